@@ -1,7 +1,8 @@
 //! # Commands
 //!
 //! This module defines the commands that can be sent to the SH1106 display controller.
-//! It includes the `Command` enum and the `CommandBuffer` struct for batching commands.
+//! It includes the `Command` enum and the `CommandBuffer` struct for batching commands, and
+//! `DynCommandBuffer` for sequences built at runtime (enable the `heapless` feature).
 //!
 //! ## Example
 //!
@@ -19,8 +20,13 @@
 //! // interface.write_command(&commands).unwrap();
 //! ```
 
+use core::fmt::{self, Display};
+
 use crate::error::MiniOledError;
 
+/// The largest number of bytes a single `Command` serializes to.
+pub const MAX_COMMAND_SIZE: usize = 7;
+
 /// A buffer for storing commands to be sent to the display.
 ///
 /// This struct holds an array of `Command`s.
@@ -42,32 +48,212 @@ impl<const N: usize> From<[Command; N]> for CommandBuffer<N> {
 }
 
 impl<const N: usize> CommandBuffer<N> {
+    /// Combines this buffer with another one, keeping the order of the commands.
+    ///
+    /// The size of the result is inferred from the context and must be the sum of both
+    /// sizes, which is checked at compile time.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mini_oled::command::{Command, CommandBuffer};
+    ///
+    /// let power_on: CommandBuffer<2> = [Command::EnableChargePump, Command::TurnDisplayOn].into();
+    /// let rotation: CommandBuffer<2> =
+    ///     [Command::EnableSegmentRemap, Command::EnableReverseComDir].into();
+    ///
+    /// let sequence: CommandBuffer<4> = power_on.concat(rotation);
+    /// ```
+    pub fn concat<const M: usize, const S: usize>(
+        self,
+        other: CommandBuffer<M>,
+    ) -> CommandBuffer<S> {
+        const {
+            assert!(
+                N + M == S,
+                "concatenated buffer size must be the sum of both sizes"
+            )
+        };
+
+        CommandBuffer {
+            buffer: core::array::from_fn(|i| {
+                if i < N {
+                    self.buffer[i]
+                } else {
+                    other.buffer[i - N]
+                }
+            }),
+        }
+    }
+
+    /// Returns an iterator over the commands, in the order they are sent.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mini_oled::command::{Command, CommandBuffer};
+    ///
+    /// let commands: CommandBuffer<2> = [Command::TurnDisplayOff, Command::Contrast(0x10)].into();
+    /// assert_eq!(commands.iter().map(Command::describe).last(), Some("Contrast"));
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = &Command> {
+        self.buffer.iter()
+    }
+
+    /// Returns the number of commands in the buffer.
+    pub const fn len(&self) -> usize {
+        N
+    }
+
+    /// Returns `true` if the buffer holds no commands.
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    #[cfg(any(test, feature = "mock"))]
+    pub(crate) fn as_slice(&self) -> &[Command] {
+        &self.buffer
+    }
+
+    /// Checks the arguments of every command in the buffer, see `Command::validate`.
+    pub fn validate(&self) -> Result<(), MiniOledError> {
+        self.buffer.iter().try_for_each(Command::validate)
+    }
+
     /// Serializes the command buffer into a byte slice.
     ///
+    /// The first byte of `buffer` is left untouched so the interface can put its control byte there.
+    ///
     /// # Arguments
     ///
     /// * `buffer` - A mutable byte slice to write the serialized commands into.
     ///
     /// # Returns
     ///
-    /// A slice containing the written bytes on success, or `MiniOledError` if the buffer is too small.
+    /// A slice containing the written bytes on success, `MiniOledError::CommandBufferSizeError`
+    /// if the buffer is too small, or `MiniOledError::InvalidCommandArgument` if a command
+    /// argument is out of range.
     pub fn to_bytes<'a>(&self, buffer: &'a mut [u8]) -> Result<&'a [u8], MiniOledError> {
-        let mut output_length = 1usize;
-        for command in &self.buffer {
-            let (command_bytes, bytes_length) = command.to_bytes();
-            if output_length + bytes_length > buffer.len() {
-                return Err(MiniOledError::CommandBufferSizeError);
-            }
-            buffer[output_length..output_length + bytes_length]
-                .copy_from_slice(&command_bytes[0..bytes_length]);
-            output_length += bytes_length;
+        serialize_commands(&self.buffer, buffer)
+    }
+}
+
+/// A command buffer whose length is only known at runtime, holding up to `CAP` commands.
+///
+/// This is useful when the commands depend on data, like an initialization sequence built
+/// from a configuration. It is backed by a `heapless::Vec`, so it doesn't allocate. Send it
+/// with `Sh1106::send_dyn_commands`.
+///
+/// # Example
+///
+/// ```rust
+/// use mini_oled::command::{Command, DynCommandBuffer};
+///
+/// let mut commands: DynCommandBuffer<8> = DynCommandBuffer::new();
+/// commands.push(Command::TurnDisplayOff).unwrap();
+/// commands
+///     .extend([Command::Contrast(0x20), Command::TurnDisplayOn])
+///     .unwrap();
+///
+/// let mut buffer = [0u8; 8];
+/// assert_eq!(commands.to_bytes(&mut buffer).unwrap()[1..], [0xAE, 0x81, 0x20, 0xAF]);
+/// ```
+#[cfg(feature = "heapless")]
+#[derive(Debug, Clone, Default)]
+pub struct DynCommandBuffer<const CAP: usize> {
+    buffer: heapless::Vec<Command, CAP>,
+}
+
+#[cfg(feature = "heapless")]
+impl<const CAP: usize> DynCommandBuffer<CAP> {
+    /// Creates an empty buffer.
+    pub const fn new() -> Self {
+        DynCommandBuffer {
+            buffer: heapless::Vec::new(),
+        }
+    }
+
+    /// Appends a command.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` on success, or `MiniOledError::CommandBufferSizeError` if the buffer already
+    /// holds `CAP` commands.
+    pub fn push(&mut self, command: Command) -> Result<(), MiniOledError> {
+        self.buffer
+            .push(command)
+            .map_err(|_| MiniOledError::CommandBufferSizeError)
+    }
+
+    /// Appends every command of `commands`, in order.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` on success, or `MiniOledError::CommandBufferSizeError` if the buffer fills up.
+    /// The commands pushed before that are kept.
+    pub fn extend(
+        &mut self,
+        commands: impl IntoIterator<Item = Command>,
+    ) -> Result<(), MiniOledError> {
+        commands
+            .into_iter()
+            .try_for_each(|command| self.push(command))
+    }
+
+    /// Removes every command from the buffer.
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+    }
+
+    /// Returns an iterator over the commands, in the order they are sent.
+    pub fn iter(&self) -> impl Iterator<Item = &Command> {
+        self.buffer.iter()
+    }
+
+    /// Returns the number of commands in the buffer.
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Returns `true` if the buffer holds no commands.
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    /// Checks the arguments of every command in the buffer, see `Command::validate`.
+    pub fn validate(&self) -> Result<(), MiniOledError> {
+        self.buffer.iter().try_for_each(Command::validate)
+    }
+
+    /// Serializes the command buffer into a byte slice, like `CommandBuffer::to_bytes`.
+    ///
+    /// The first byte of `buffer` is left untouched so the interface can put its control byte there.
+    pub fn to_bytes<'a>(&self, buffer: &'a mut [u8]) -> Result<&'a [u8], MiniOledError> {
+        serialize_commands(&self.buffer, buffer)
+    }
+}
+
+/// Serializes `commands` into `buffer` after its first byte, see `CommandBuffer::to_bytes`.
+fn serialize_commands<'a>(
+    commands: &[Command],
+    buffer: &'a mut [u8],
+) -> Result<&'a [u8], MiniOledError> {
+    let mut output_length = 1usize;
+    for command in commands {
+        command.validate()?;
+        let (command_bytes, bytes_length) = command.to_bytes();
+        if output_length + bytes_length > buffer.len() {
+            return Err(MiniOledError::CommandBufferSizeError);
         }
-        Ok(&buffer[..output_length])
+        buffer[output_length..output_length + bytes_length]
+            .copy_from_slice(&command_bytes[0..bytes_length]);
+        output_length += bytes_length;
     }
+    Ok(&buffer[..output_length])
 }
 
 /// Enum representing commands that can be sent to the SH1106 controller.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Command {
     /// Set contrast. Higher number is higher contrast.
     /// Default is `0x7F`.
@@ -91,10 +277,14 @@ pub enum Command {
     /// In sleep mode (0xAE), the internal circuit is active but the driving circuit is off,
     /// reducing power consumption drastically (< 20µA). RAM content is preserved.
     TurnDisplayOff,
-    /// Set column address lower 4 bits.
+    /// Set column address lower 4 bits, from 0-15.
     ColumnAddressLow(u8),
-    /// Set column address higher 4 bits.
+    /// Set column address higher 4 bits, from 0-15.
     ColumnAddressHigh(u8),
+    /// Set column address, sent as `ColumnAddressLow` followed by `ColumnAddressHigh`.
+    /// `Command::from_bytes` parses its bytes back as those two commands. The SH1106 has 132
+    /// columns, so only 0-131 are valid.
+    ColumnAddress(u8),
     /// Set page address.
     PageAddress(Page),
     /// Set display start line from 0-63.
@@ -112,7 +302,7 @@ pub enum Command {
     EnableReverseComDir,
     /// Disable reverse COM direction (normal scan).
     DisableReverseComDir,
-    /// Set vertical display offset.
+    /// Set vertical display offset from 0-63.
     DisplayOffset(u8),
     /// Setup COM hardware configuration.
     /// Value indicates sequential (`SequentialComPinConfig`) or alternative (`AlternativeComPinConfig`)
@@ -123,59 +313,351 @@ pub enum Command {
     SequentialComPinConfig,
     /// Set up display clock.
     /// First value is oscillator frequency, increasing with higher value.
-    /// Second value is divide ratio - 1.
+    /// Second value is divide ratio - 1. Each value is from 0-15.
     DisplayClockDiv(u8, u8),
-    /// Set up phase 1 and 2 of precharge period. Each value is from 0-63.
+    /// Set up phase 1 and 2 of precharge period. Each value is from 0-15.
     PreChargePeriod(u8, u8),
     /// Set Vcomh Deselect level.
     VcomhDeselect(VcomhLevel),
     /// No Operation.
     Noop,
+    /// Start read-modify-write mode.
+    /// The column address only increments on writes, so a column can be read and written
+    /// back without setting the address again.
+    ReadModifyWriteStart,
+    /// End read-modify-write mode, restoring the column address it was started at.
+    ReadModifyWriteEnd,
     /// Enable charge pump.
     /// Display must be off when performing this command.
     /// Default is `EnableChargePump`.
     EnableChargePump,
     /// Disable charge pump.
     DisableChargePump,
+    /// Enable the charge pump of SSD1306 controllers, which use a different opcode.
+    /// Display must be off when performing this command.
+    EnableSsd1306ChargePump,
+    /// Disable the charge pump of SSD1306 controllers.
+    DisableSsd1306ChargePump,
+    /// Set the output voltage of the SH1106 charge pump.
+    /// Default is `PumpVoltage::V80`.
+    PumpVoltage(PumpVoltage),
+    /// Set up continuous horizontal scroll.
+    /// First value is the scroll direction, second is the start page,
+    /// third is the interval between scroll steps in frames and fourth is the end page.
+    /// Scrolling must be disabled with `DisableScroll` before sending this command.
+    /// SSD1306 only, the SH1106 has no scroll commands.
+    HorizontalScrollSetup(HorizontalScrollDirection, Page, NFrames, Page),
+    /// Set the rows moved by vertical scrolling.
+    /// The first `top_fixed_rows` rows (0-63) stay in place and the next `scroll_rows`
+    /// rows (0-64) scroll.
+    /// SSD1306 only, on the SH1106 this opcode is not a command.
+    VerticalScrollArea {
+        /// Number of rows at the top that don't scroll.
+        top_fixed_rows: u8,
+        /// Number of rows in the scroll area.
+        scroll_rows: u8,
+    },
+    /// Set up continuous vertical and horizontal scroll.
+    /// The pages from `start_page` to `end_page` move horizontally, and the vertical scroll
+    /// area moves up by `vertical_offset` rows (0-63) on each step.
+    /// Scrolling must be disabled with `DisableScroll` before sending this command.
+    /// SSD1306 only, the SH1106 has no scroll commands.
+    VerticalAndHorizontalScrollSetup {
+        /// Horizontal direction of the scroll.
+        direction: HorizontalScrollDirection,
+        /// First page that scrolls horizontally.
+        start_page: Page,
+        /// Interval between scroll steps in frames.
+        interval: NFrames,
+        /// Last page that scrolls horizontally.
+        end_page: Page,
+        /// Rows moved vertically on each scroll step.
+        vertical_offset: u8,
+    },
+    /// Start scrolling with the parameters set by the last scroll setup command.
+    /// SSD1306 only.
+    EnableScroll,
+    /// Stop scrolling. RAM content must be rewritten after this command.
+    /// SSD1306 only.
+    DisableScroll,
 }
 
 impl Command {
-    pub fn to_bytes(&self) -> ([u8; 2], usize) {
-        match self {
-            Command::Contrast(val) => ([0x81, *val], self.get_byte_size()),
-            Command::EnableTestScreen => ([0xA5, 0], self.get_byte_size()),
-            Command::DisableTestScreen => ([0xA4, 0], self.get_byte_size()),
-            Command::PositiveImageMode => ([0xA6, 0], self.get_byte_size()),
-            Command::NegativeImageMode => ([0xA7, 0], self.get_byte_size()),
-            Command::TurnDisplayOn => ([0xAF, 0], self.get_byte_size()),
-            Command::TurnDisplayOff => ([0xAE, 0], self.get_byte_size()),
-            Command::ColumnAddressLow(addr) => ([0xF & addr, 0], self.get_byte_size()),
-            Command::ColumnAddressHigh(addr) => ([0x10 | (0xF & addr), 0], self.get_byte_size()),
-            Command::PageAddress(page) => ([0xB0 | (*page as u8), 0], self.get_byte_size()),
-            Command::StartLine(line) => ([0x40 | (0x3F & line), 0], self.get_byte_size()),
-            Command::EnableSegmentRemap => ([0xA1, 0], self.get_byte_size()),
-            Command::DisableSegmentRemap => ([0xA0, 0], self.get_byte_size()),
-            Command::Multiplex(ratio) => ([0xA8, *ratio], self.get_byte_size()),
-            Command::EnableReverseComDir => ([0xC8, 0], self.get_byte_size()),
-            Command::DisableReverseComDir => ([0xC0, 0], self.get_byte_size()),
-            Command::DisplayOffset(offset) => ([0xD3, *offset], self.get_byte_size()),
-            Command::AlternativeComPinConfig => ([0xDA, 0x12], self.get_byte_size()),
-            Command::SequentialComPinConfig => ([0xDA, 0x02], self.get_byte_size()),
-            Command::DisplayClockDiv(fosc, div) => (
-                [0xD5, ((0xF & fosc) << 4) | (0xF & div)],
-                self.get_byte_size(),
-            ),
-            Command::PreChargePeriod(phase1, phase2) => (
-                [0xD9, ((0xF & phase2) << 4) | (0xF & phase1)],
-                self.get_byte_size(),
-            ),
-            Command::VcomhDeselect(level) => ([0xDB, (*level as u8) << 4], self.get_byte_size()),
-            Command::Noop => ([0xE3, 0], self.get_byte_size()),
-            Command::EnableChargePump => ([0xAD, 0x8B], self.get_byte_size()),
-            Command::DisableChargePump => ([0xAD, 0x8A], self.get_byte_size()),
+    /// Serializes the command into its opcode bytes.
+    ///
+    /// # Returns
+    ///
+    /// A fixed-size array holding the command bytes, and the number of bytes actually used.
+    /// The array holds `MAX_COMMAND_SIZE` bytes, enough for the longest scroll setup command.
+    pub fn to_bytes(&self) -> ([u8; MAX_COMMAND_SIZE], usize) {
+        let command_bytes: &[u8] = match self {
+            Command::Contrast(val) => &[0x81, *val],
+            Command::EnableTestScreen => &[0xA5],
+            Command::DisableTestScreen => &[0xA4],
+            Command::PositiveImageMode => &[0xA6],
+            Command::NegativeImageMode => &[0xA7],
+            Command::TurnDisplayOn => &[0xAF],
+            Command::TurnDisplayOff => &[0xAE],
+            Command::ColumnAddressLow(addr) => &[0xF & addr],
+            Command::ColumnAddressHigh(addr) => &[0x10 | (0xF & addr)],
+            Command::ColumnAddress(column) => &[0xF & column, 0x10 | (column >> 4)],
+            Command::PageAddress(page) => &[0xB0 | (*page as u8)],
+            Command::StartLine(line) => &[0x40 | (0x3F & line)],
+            Command::EnableSegmentRemap => &[0xA1],
+            Command::DisableSegmentRemap => &[0xA0],
+            Command::Multiplex(ratio) => &[0xA8, *ratio],
+            Command::EnableReverseComDir => &[0xC8],
+            Command::DisableReverseComDir => &[0xC0],
+            Command::DisplayOffset(offset) => &[0xD3, *offset],
+            Command::AlternativeComPinConfig => &[0xDA, 0x12],
+            Command::SequentialComPinConfig => &[0xDA, 0x02],
+            Command::DisplayClockDiv(fosc, div) => &[0xD5, ((0xF & fosc) << 4) | (0xF & div)],
+            Command::PreChargePeriod(phase1, phase2) => {
+                &[0xD9, ((0xF & phase2) << 4) | (0xF & phase1)]
+            }
+            Command::VcomhDeselect(level) => &[0xDB, (*level as u8) << 4],
+            Command::Noop => &[0xE3],
+            Command::ReadModifyWriteStart => &[0xE0],
+            Command::ReadModifyWriteEnd => &[0xEE],
+            Command::EnableChargePump => &[0xAD, 0x8B],
+            Command::DisableChargePump => &[0xAD, 0x8A],
+            Command::EnableSsd1306ChargePump => &[0x8D, 0x14],
+            Command::DisableSsd1306ChargePump => &[0x8D, 0x10],
+            Command::PumpVoltage(voltage) => &[0x30 | (*voltage as u8)],
+            Command::HorizontalScrollSetup(direction, start_page, interval, end_page) => &[
+                *direction as u8,
+                0x00,
+                *start_page as u8,
+                *interval as u8,
+                *end_page as u8,
+                0x00,
+                0xFF,
+            ],
+            Command::VerticalScrollArea {
+                top_fixed_rows,
+                scroll_rows,
+            } => &[0xA3, 0x3F & top_fixed_rows, 0x7F & scroll_rows],
+            Command::VerticalAndHorizontalScrollSetup {
+                direction,
+                start_page,
+                interval,
+                end_page,
+                vertical_offset,
+            } => &[
+                // 0x29 and 0x2A follow the horizontal only opcodes 0x26 and 0x27.
+                *direction as u8 + 3,
+                0x00,
+                *start_page as u8,
+                *interval as u8,
+                *end_page as u8,
+                0x3F & vertical_offset,
+            ],
+            Command::EnableScroll => &[0x2F],
+            Command::DisableScroll => &[0x2E],
+        };
+
+        let mut bytes = [0u8; MAX_COMMAND_SIZE];
+        bytes[..command_bytes.len()].copy_from_slice(command_bytes);
+        (bytes, self.get_byte_size())
+    }
+
+    /// Checks that the arguments of the command are inside their documented range.
+    ///
+    /// `to_bytes` masks the arguments to the bits of the opcode, so an out of range value
+    /// would otherwise be sent silently truncated.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if every argument is valid, or `MiniOledError::InvalidCommandArgument` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mini_oled::{command::Command, error::MiniOledError};
+    ///
+    /// assert_eq!(Command::StartLine(63).validate(), Ok(()));
+    /// assert_eq!(
+    ///     Command::StartLine(64).validate(),
+    ///     Err(MiniOledError::InvalidCommandArgument)
+    /// );
+    /// ```
+    pub const fn validate(&self) -> Result<(), MiniOledError> {
+        let valid = match *self {
+            Command::ColumnAddressLow(addr) | Command::ColumnAddressHigh(addr) => addr <= 0xF,
+            Command::ColumnAddress(column) => column < 132,
+            Command::StartLine(line) => line <= 63,
+            Command::Multiplex(ratio) => ratio >= 15 && ratio <= 63,
+            Command::DisplayOffset(offset) => offset <= 63,
+            Command::DisplayClockDiv(fosc, div) => fosc <= 0xF && div <= 0xF,
+            Command::PreChargePeriod(phase1, phase2) => phase1 <= 0xF && phase2 <= 0xF,
+            Command::VerticalScrollArea {
+                top_fixed_rows,
+                scroll_rows,
+            } => top_fixed_rows <= 63 && scroll_rows <= 64,
+            Command::VerticalAndHorizontalScrollSetup {
+                vertical_offset, ..
+            } => vertical_offset <= 63,
+            _ => true,
+        };
+
+        match valid {
+            true => Ok(()),
+            false => Err(MiniOledError::InvalidCommandArgument),
         }
     }
 
+    /// Parses the first command in `bytes`.
+    ///
+    /// This is the inverse of `to_bytes` and is useful for decoding command streams.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The serialized command bytes, without the interface control byte.
+    ///
+    /// # Returns
+    ///
+    /// The parsed command and the number of bytes it consumed on success,
+    /// `MiniOledError::UnknownCommandError` if the opcode or its argument is not recognized,
+    /// or `MiniOledError::CommandBufferSizeError` if `bytes` ends before the command does.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mini_oled::command::Command;
+    ///
+    /// let (command, length) = Command::from_bytes(&[0x81, 0x80, 0xAF]).unwrap();
+    /// assert_eq!(command, Command::Contrast(0x80));
+    /// assert_eq!(length, 2);
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<(Command, usize), MiniOledError> {
+        let opcode = *bytes.first().ok_or(MiniOledError::CommandBufferSizeError)?;
+        let argument = |index: usize| {
+            bytes
+                .get(index)
+                .copied()
+                .ok_or(MiniOledError::CommandBufferSizeError)
+        };
+
+        let command = match opcode {
+            0x00..=0x0F => Command::ColumnAddressLow(opcode & 0xF),
+            0x10..=0x1F => Command::ColumnAddressHigh(opcode & 0xF),
+            0x26 | 0x27 => {
+                let direction = match opcode {
+                    0x26 => HorizontalScrollDirection::Right,
+                    _ => HorizontalScrollDirection::Left,
+                };
+                let interval = NFrames::try_from(argument(3)?)
+                    .map_err(|_| MiniOledError::UnknownCommandError)?;
+                let start_page = argument(2)?;
+                let end_page = argument(4)?;
+                if argument(1)? != 0x00
+                    || start_page > 7
+                    || end_page > 7
+                    || argument(5)? != 0x00
+                    || argument(6)? != 0xFF
+                {
+                    return Err(MiniOledError::UnknownCommandError);
+                }
+                Command::HorizontalScrollSetup(
+                    direction,
+                    Page::from(start_page),
+                    interval,
+                    Page::from(end_page),
+                )
+            }
+            0x29 | 0x2A => {
+                let direction = match opcode {
+                    0x29 => HorizontalScrollDirection::Right,
+                    _ => HorizontalScrollDirection::Left,
+                };
+                let interval = NFrames::try_from(argument(3)?)
+                    .map_err(|_| MiniOledError::UnknownCommandError)?;
+                let start_page = argument(2)?;
+                let end_page = argument(4)?;
+                let vertical_offset = argument(5)?;
+                if argument(1)? != 0x00 || start_page > 7 || end_page > 7 || vertical_offset > 63 {
+                    return Err(MiniOledError::UnknownCommandError);
+                }
+                Command::VerticalAndHorizontalScrollSetup {
+                    direction,
+                    start_page: Page::from(start_page),
+                    interval,
+                    end_page: Page::from(end_page),
+                    vertical_offset,
+                }
+            }
+            0x2E => Command::DisableScroll,
+            0x2F => Command::EnableScroll,
+            0x30..=0x33 => Command::PumpVoltage(PumpVoltage::from_bits(opcode & 0x3)),
+            0x40..=0x7F => Command::StartLine(opcode & 0x3F),
+            0x81 => Command::Contrast(argument(1)?),
+            0x8D => match argument(1)? {
+                0x14 => Command::EnableSsd1306ChargePump,
+                0x10 => Command::DisableSsd1306ChargePump,
+                _ => return Err(MiniOledError::UnknownCommandError),
+            },
+            0xA0 => Command::DisableSegmentRemap,
+            0xA1 => Command::EnableSegmentRemap,
+            0xA3 => {
+                let top_fixed_rows = argument(1)?;
+                let scroll_rows = argument(2)?;
+                // The ranges of `validate`, so parsed commands can be sent again.
+                if top_fixed_rows > 63 || scroll_rows > 64 {
+                    return Err(MiniOledError::UnknownCommandError);
+                }
+                Command::VerticalScrollArea {
+                    top_fixed_rows,
+                    scroll_rows,
+                }
+            }
+            0xA4 => Command::DisableTestScreen,
+            0xA5 => Command::EnableTestScreen,
+            0xA6 => Command::PositiveImageMode,
+            0xA7 => Command::NegativeImageMode,
+            0xA8 => Command::Multiplex(argument(1)?),
+            0xAD => match argument(1)? {
+                0x8B => Command::EnableChargePump,
+                0x8A => Command::DisableChargePump,
+                _ => return Err(MiniOledError::UnknownCommandError),
+            },
+            0xAE => Command::TurnDisplayOff,
+            0xAF => Command::TurnDisplayOn,
+            0xB0..=0xB7 => Command::PageAddress(Page::from(opcode)),
+            0xC0 => Command::DisableReverseComDir,
+            0xC8 => Command::EnableReverseComDir,
+            0xD3 => Command::DisplayOffset(argument(1)?),
+            0xD5 => {
+                let value = argument(1)?;
+                Command::DisplayClockDiv(value >> 4, value & 0xF)
+            }
+            0xD9 => {
+                let value = argument(1)?;
+                Command::PreChargePeriod(value & 0xF, value >> 4)
+            }
+            0xDA => match argument(1)? {
+                0x12 => Command::AlternativeComPinConfig,
+                0x02 => Command::SequentialComPinConfig,
+                _ => return Err(MiniOledError::UnknownCommandError),
+            },
+            0xDB => {
+                let value = argument(1)?;
+                if value & 0xF != 0 {
+                    return Err(MiniOledError::UnknownCommandError);
+                }
+                Command::VcomhDeselect(
+                    VcomhLevel::try_from(value >> 4)
+                        .map_err(|_| MiniOledError::UnknownCommandError)?,
+                )
+            }
+            0xE3 => Command::Noop,
+            0xE0 => Command::ReadModifyWriteStart,
+            0xEE => Command::ReadModifyWriteEnd,
+            _ => return Err(MiniOledError::UnknownCommandError),
+        };
+
+        Ok((command, command.get_byte_size()))
+    }
+
     /// Returns the size in bytes of the command when serialized.
     pub const fn get_byte_size(&self) -> usize {
         match self {
@@ -188,6 +670,7 @@ impl Command {
             Command::TurnDisplayOff => 1,
             Command::ColumnAddressLow(_) => 1,
             Command::ColumnAddressHigh(_) => 1,
+            Command::ColumnAddress(_) => 2,
             Command::PageAddress(_) => 1,
             Command::StartLine(_) => 1,
             Command::EnableSegmentRemap => 1,
@@ -202,9 +685,86 @@ impl Command {
             Command::PreChargePeriod(_, _) => 2,
             Command::VcomhDeselect(_) => 2,
             Command::Noop => 1,
+            Command::ReadModifyWriteStart => 1,
+            Command::ReadModifyWriteEnd => 1,
             Command::EnableChargePump => 2,
             Command::DisableChargePump => 2,
+            Command::EnableSsd1306ChargePump => 2,
+            Command::DisableSsd1306ChargePump => 2,
+            Command::PumpVoltage(_) => 1,
+            Command::HorizontalScrollSetup(_, _, _, _) => 7,
+            Command::VerticalScrollArea { .. } => 3,
+            Command::VerticalAndHorizontalScrollSetup { .. } => 6,
+            Command::EnableScroll => 1,
+            Command::DisableScroll => 1,
+        }
+    }
+
+    /// Returns the mnemonic of the command, without its arguments.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mini_oled::command::Command;
+    ///
+    /// assert_eq!(Command::Contrast(0x80).describe(), "Contrast");
+    /// ```
+    pub const fn describe(&self) -> &'static str {
+        match self {
+            Command::Contrast(_) => "Contrast",
+            Command::EnableTestScreen => "EnableTestScreen",
+            Command::DisableTestScreen => "DisableTestScreen",
+            Command::PositiveImageMode => "PositiveImageMode",
+            Command::NegativeImageMode => "NegativeImageMode",
+            Command::TurnDisplayOn => "TurnDisplayOn",
+            Command::TurnDisplayOff => "TurnDisplayOff",
+            Command::ColumnAddressLow(_) => "ColumnAddressLow",
+            Command::ColumnAddressHigh(_) => "ColumnAddressHigh",
+            Command::ColumnAddress(_) => "ColumnAddress",
+            Command::PageAddress(_) => "PageAddress",
+            Command::StartLine(_) => "StartLine",
+            Command::EnableSegmentRemap => "EnableSegmentRemap",
+            Command::DisableSegmentRemap => "DisableSegmentRemap",
+            Command::Multiplex(_) => "Multiplex",
+            Command::EnableReverseComDir => "EnableReverseComDir",
+            Command::DisableReverseComDir => "DisableReverseComDir",
+            Command::DisplayOffset(_) => "DisplayOffset",
+            Command::AlternativeComPinConfig => "AlternativeComPinConfig",
+            Command::SequentialComPinConfig => "SequentialComPinConfig",
+            Command::DisplayClockDiv(_, _) => "DisplayClockDiv",
+            Command::PreChargePeriod(_, _) => "PreChargePeriod",
+            Command::VcomhDeselect(_) => "VcomhDeselect",
+            Command::Noop => "Noop",
+            Command::ReadModifyWriteStart => "ReadModifyWriteStart",
+            Command::ReadModifyWriteEnd => "ReadModifyWriteEnd",
+            Command::EnableChargePump => "EnableChargePump",
+            Command::DisableChargePump => "DisableChargePump",
+            Command::EnableSsd1306ChargePump => "EnableSsd1306ChargePump",
+            Command::DisableSsd1306ChargePump => "DisableSsd1306ChargePump",
+            Command::PumpVoltage(_) => "PumpVoltage",
+            Command::HorizontalScrollSetup(_, _, _, _) => "HorizontalScrollSetup",
+            Command::VerticalScrollArea { .. } => "VerticalScrollArea",
+            Command::VerticalAndHorizontalScrollSetup { .. } => "VerticalAndHorizontalScrollSetup",
+            Command::EnableScroll => "EnableScroll",
+            Command::DisableScroll => "DisableScroll",
+        }
+    }
+}
+
+/// Formats the command with its arguments and serialized bytes, like `Contrast(128) -> [0x81, 0x80]`.
+///
+/// This is meant for logging command streams, for example from a mock interface.
+impl Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (command_bytes, bytes_length) = self.to_bytes();
+        write!(f, "{:?} -> [", self)?;
+        for (i, byte) in command_bytes[..bytes_length].iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "0x{:02X}", byte)?;
         }
+        write!(f, "]")
     }
 }
 
@@ -221,7 +781,7 @@ impl Command {
 /// assert_eq!(page as u8, 0);
 /// ```
 #[repr(u8)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Page {
     /// Page 0
     Page0 = 0,
@@ -251,25 +811,55 @@ impl Page {
     pub fn all() -> impl Iterator<Item = Page> {
         (0..8).map(Page::from)
     }
+
+    /// Returns the page holding pixel row `y`.
+    ///
+    /// Rows past 63 wrap around like `Page::from`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mini_oled::command::Page;
+    ///
+    /// assert_eq!(Page::from_y(17), Page::Page2);
+    /// ```
+    pub fn from_y(y: u32) -> Page {
+        Page::from((y >> 3) as u8)
+    }
+
+    /// Returns the index of the first byte of this page in a buffer `width` columns wide.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mini_oled::command::Page;
+    ///
+    /// assert_eq!(Page::Page2.byte_offset(128), 256);
+    /// ```
+    pub fn byte_offset(self, width: u32) -> usize {
+        self as usize * width as usize
+    }
 }
 
 impl From<u8> for Page {
     fn from(val: u8) -> Page {
-        // Faster way the casting u8 to Page
-        // ```rust
-        // 0x00 => Page::Page0,
-        // 0x08 => Page::Page1,
-        // 0x09 => Page::Page2,
-        // 0x0A => Page::Page3,
-        // ```
-        let new_val = val & 0b111;
-        unsafe { core::mem::transmute(new_val) }
+        // Only the lowest 3 bits are used, so every input maps to a page.
+        match val & 0b111 {
+            0 => Page::Page0,
+            1 => Page::Page1,
+            2 => Page::Page2,
+            3 => Page::Page3,
+            4 => Page::Page4,
+            5 => Page::Page5,
+            6 => Page::Page6,
+            _ => Page::Page7,
+        }
     }
 }
 
-/// Frame interval configuration for the display clock.
+/// Frame interval between scroll steps.
 ///
-/// This determines how often the display refreshes.
+/// Used by the scroll setup commands to determine how fast the content scrolls.
 ///
 /// # Example
 ///
@@ -279,8 +869,7 @@ impl From<u8> for Page {
 /// let frames = NFrames::F5;
 /// ```
 #[repr(u8)]
-#[derive(Debug, Clone, Copy)]
-
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum NFrames {
     /// 2 Frames
     F2 = 0b111,
@@ -300,6 +889,37 @@ pub enum NFrames {
     F256 = 0b011,
 }
 
+/// Converts the interval bits of a scroll setup command back into `NFrames`.
+///
+/// Unlike `Page`, the value isn't masked, so anything above `0b111` is rejected with
+/// `MiniOledError::InvalidCommandArgument`.
+///
+/// # Example
+///
+/// ```rust
+/// use mini_oled::command::NFrames;
+///
+/// assert_eq!(NFrames::try_from(0b110), Ok(NFrames::F25));
+/// assert!(NFrames::try_from(0b1000).is_err());
+/// ```
+impl TryFrom<u8> for NFrames {
+    type Error = MiniOledError;
+
+    fn try_from(bits: u8) -> Result<NFrames, MiniOledError> {
+        match bits {
+            0b111 => Ok(NFrames::F2),
+            0b100 => Ok(NFrames::F3),
+            0b101 => Ok(NFrames::F4),
+            0b000 => Ok(NFrames::F5),
+            0b110 => Ok(NFrames::F25),
+            0b001 => Ok(NFrames::F64),
+            0b010 => Ok(NFrames::F128),
+            0b011 => Ok(NFrames::F256),
+            _ => Err(MiniOledError::InvalidCommandArgument),
+        }
+    }
+}
+
 /// Vcomh Deselect level.
 ///
 /// This adjusts the Vcomh regulator output.
@@ -312,8 +932,7 @@ pub enum NFrames {
 /// let level = VcomhLevel::V077;
 /// ```
 #[repr(u8)]
-#[derive(Debug, Clone, Copy)]
-
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum VcomhLevel {
     /// 0.65 * Vcc
     V065 = 0b001,
@@ -324,3 +943,85 @@ pub enum VcomhLevel {
     /// Auto
     Auto = 0b100,
 }
+
+/// Converts the level bits of a `VcomhDeselect` command, without the shift, back into
+/// `VcomhLevel`.
+///
+/// Unlike `Page`, the value isn't masked, so any other bit pattern is rejected with
+/// `MiniOledError::InvalidCommandArgument`.
+///
+/// # Example
+///
+/// ```rust
+/// use mini_oled::command::VcomhLevel;
+///
+/// assert_eq!(VcomhLevel::try_from(0b100), Ok(VcomhLevel::Auto));
+/// assert!(VcomhLevel::try_from(0b000).is_err());
+/// ```
+impl TryFrom<u8> for VcomhLevel {
+    type Error = MiniOledError;
+
+    fn try_from(bits: u8) -> Result<VcomhLevel, MiniOledError> {
+        match bits {
+            0b001 => Ok(VcomhLevel::V065),
+            0b010 => Ok(VcomhLevel::V077),
+            0b011 => Ok(VcomhLevel::V083),
+            0b100 => Ok(VcomhLevel::Auto),
+            _ => Err(MiniOledError::InvalidCommandArgument),
+        }
+    }
+}
+
+/// Output voltage of the SH1106 charge pump.
+///
+/// A higher voltage makes the panel brighter, which helps on boards where the default level
+/// is too dim.
+///
+/// # Example
+///
+/// ```rust
+/// use mini_oled::command::PumpVoltage;
+///
+/// let voltage = PumpVoltage::V90;
+/// ```
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PumpVoltage {
+    /// 6.4V
+    V64 = 0b00,
+    /// 7.4V
+    V74 = 0b01,
+    /// 8.0V
+    V80 = 0b10,
+    /// 9.0V
+    V90 = 0b11,
+}
+
+impl PumpVoltage {
+    pub(crate) const fn from_bits(bits: u8) -> PumpVoltage {
+        match bits & 0b11 {
+            0b00 => PumpVoltage::V64,
+            0b01 => PumpVoltage::V74,
+            0b10 => PumpVoltage::V80,
+            _ => PumpVoltage::V90,
+        }
+    }
+}
+
+/// Horizontal scroll direction.
+///
+/// # Example
+///
+/// ```rust
+/// use mini_oled::command::HorizontalScrollDirection;
+///
+/// let direction = HorizontalScrollDirection::Left;
+/// ```
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HorizontalScrollDirection {
+    /// Scroll content to the right
+    Right = 0x26,
+    /// Scroll content to the left
+    Left = 0x27,
+}
@@ -21,8 +21,27 @@
 //!         Err(MiniOledError::I2cError(_)) => {
 //!             // Handle I2C communication error
 //!         },
-//!         Err(MiniOledError::SpiBusError(_)) => {
-//!             // Handle SPI communication error
+//!         Err(MiniOledError::PixelOutOfBounds { .. }) => {
+//!             // Handle drawing outside of the display
+//!         },
+//!         Err(MiniOledError::UnknownCommandError) => {
+//!             // Handle unrecognized command bytes
+//!         },
+//!         Err(MiniOledError::InvalidCommandArgument) => {
+//!             // Handle a command argument outside of its valid range
+//!         },
+//!         Err(MiniOledError::PinError(_)) => {
+//!             // Handle a GPIO pin error, like the reset pin
+//!         },
+//!         Err(MiniOledError::UnsupportedOperation) => {
+//!             // Handle an operation the interface can't do, like reading over SPI
+//!         },
+//!         Err(MiniOledError::InitializationError) => {
+//!             // Handle a display that didn't turn on after `init_verified`
+//!         },
+//!         Err(_) => {
+//!             // `MiniOledError` is non-exhaustive, new errors may be added and
+//!             // `SpiBusError` only exists with the `spi` feature
 //!         },
 //!     }
 //! }
@@ -33,9 +52,14 @@ use core::{
     fmt::{self, Display},
 };
 
-use embedded_hal::{i2c, spi};
+use embedded_hal::{digital, i2c};
 
-#[derive(Debug)]
+#[cfg(feature = "spi")]
+use embedded_hal::spi;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
 pub enum MiniOledError {
     /// Error when the command buffer size is exceeded.
     CommandBufferSizeError,
@@ -44,7 +68,21 @@ pub enum MiniOledError {
     /// Error wrapping an I2C communication error.
     I2cError(i2c::ErrorKind),
     /// Error wrapping an SPI communication error.
+    #[cfg(feature = "spi")]
     SpiBusError(spi::ErrorKind),
+    /// Error when a pixel coordinate is outside of the display.
+    PixelOutOfBounds { x: u32, y: u32 },
+    /// Error when command bytes can't be parsed into a `Command`.
+    UnknownCommandError,
+    /// Error when a command argument is outside of its valid range.
+    InvalidCommandArgument,
+    /// Error wrapping a GPIO pin error, like the reset pin.
+    PinError(digital::ErrorKind),
+    /// Error when the communication interface can't do the operation, like reading the
+    /// status over a write-only bus.
+    UnsupportedOperation,
+    /// Error when the display doesn't report the state the initialization sequence set.
+    InitializationError,
 }
 
 impl Display for MiniOledError {
@@ -59,11 +97,53 @@ impl Display for MiniOledError {
             MiniOledError::I2cError(error_kind) => {
                 write!(f, "Embedded Hal I2C Error: {}", error_kind)
             }
+            #[cfg(feature = "spi")]
             MiniOledError::SpiBusError(error_kind) => {
                 write!(f, "Embedded Hal Spi Bus Error: {}", error_kind)
             }
+            MiniOledError::PixelOutOfBounds { x, y } => {
+                write!(
+                    f,
+                    "Mini Oled Library Error: Pixel ({}, {}) Out Of Bounds",
+                    x, y
+                )
+            }
+            MiniOledError::UnknownCommandError => {
+                write!(f, "Mini Oled Library Error: Unknown Command")
+            }
+            MiniOledError::InvalidCommandArgument => {
+                write!(f, "Mini Oled Library Error: Invalid Command Argument")
+            }
+            MiniOledError::PinError(error_kind) => {
+                write!(f, "Embedded Hal Digital Pin Error: {}", error_kind)
+            }
+            MiniOledError::UnsupportedOperation => {
+                write!(f, "Mini Oled Library Error: Unsupported Operation")
+            }
+            MiniOledError::InitializationError => {
+                write!(f, "Mini Oled Library Error: Display Initialization Failed")
+            }
         }
     }
 }
 
 impl Error for MiniOledError {}
+
+impl From<i2c::ErrorKind> for MiniOledError {
+    fn from(error_kind: i2c::ErrorKind) -> Self {
+        MiniOledError::I2cError(error_kind)
+    }
+}
+
+#[cfg(feature = "spi")]
+impl From<spi::ErrorKind> for MiniOledError {
+    fn from(error_kind: spi::ErrorKind) -> Self {
+        MiniOledError::SpiBusError(error_kind)
+    }
+}
+
+impl From<digital::ErrorKind> for MiniOledError {
+    fn from(error_kind: digital::ErrorKind) -> Self {
+        MiniOledError::PinError(error_kind)
+    }
+}
@@ -1,11 +1,43 @@
-use embedded_hal::i2c::{Error, I2c};
+use embedded_hal::i2c::{
+    AddressMode, Error, ErrorKind, I2c, Operation, SevenBitAddress, TenBitAddress,
+};
 
-use crate::{command::CommandBuffer, error::MiniOledError};
+use crate::{
+    command::{Command, CommandBuffer},
+    error::MiniOledError,
+};
 
-use super::CommunicationInterface;
+use super::{CommunicationInterface, MAX_COMMAND_BYTES};
+
+#[cfg(feature = "async")]
+use super::AsyncCommunicationInterface;
+
+/// Size of the frame sent by `write_command`, the control byte and up to
+/// `MAX_COMMAND_BYTES` command bytes.
+///
+/// The largest sequence sent by the driver is the initialization sequence with 23 bytes.
+pub const COMMAND_FRAME_SIZE: usize = MAX_COMMAND_BYTES + 1;
+
+/// The largest number of display data bytes sent by `write_data` in one transaction.
+const MAX_DATA_BYTES: usize = 128;
+
+/// Size of the frame sent by `write_data`, the control byte and up to `MAX_DATA_BYTES`
+/// data bytes.
+const DATA_FRAME_SIZE: usize = MAX_DATA_BYTES + 1;
 
 /// I2C communication interface.
 ///
+/// `write_command` sends the whole command buffer in one transaction, so the serialized
+/// commands must fit in `COMMAND_FRAME_SIZE` - 1 bytes, otherwise it returns
+/// `MiniOledError::CommandBufferSizeError`.
+///
+/// `write_data` sends at most 128 bytes, the driver splits wider pages into several
+/// transfers. `with_max_transfer_bytes` lowers the bytes per transaction. The control byte and the data are passed to `I2c::transaction` as two write
+/// operations, which go out as one write without copying the data, see `with_data_copy`.
+///
+/// `read_status` writes the command control byte and reads the status byte back in one
+/// transaction.
+///
 /// # Example
 ///
 /// ```rust,ignore
@@ -15,50 +47,370 @@ use super::CommunicationInterface;
 /// // let i2c_driver = ...;
 /// let interface = I2cInterface::new(i2c_driver, 0x3C);
 /// ```
-pub struct I2cInterface<IC: I2c> {
+///
+/// # Shared Bus
+///
+/// `I2cInterface` accepts anything implementing `embedded_hal::i2c::I2c`, so a bus shared with
+/// other devices works through the `embedded-hal-bus` device wrappers, without extra setup.
+///
+/// ```rust,ignore
+/// use core::cell::RefCell;
+/// use embedded_hal_bus::i2c::RefCellDevice;
+/// use mini_oled::{interface::i2c::I2cInterface, screen::sh1106::Sh1106};
+///
+/// // let i2c_driver = ...;
+/// let i2c_bus = RefCell::new(i2c_driver);
+///
+/// let mut screen = Sh1106::new(I2cInterface::new(RefCellDevice::new(&i2c_bus), 0x3C));
+/// // let sensor = Sensor::new(RefCellDevice::new(&i2c_bus));
+/// ```
+///
+/// Use `AtomicDevice` or `CriticalSectionDevice` instead of `RefCellDevice` when the bus is
+/// shared between interrupt handlers or threads.
+pub struct I2cInterface<IC, A = SevenBitAddress> {
     i2c: IC,
-    address: u8,
+    address: A,
+    command_control_byte: u8,
+    data_control_byte: u8,
+    retries: u8,
+    data_copy: bool,
+    max_transfer_bytes: usize,
 }
 
-impl<IC: I2c> I2cInterface<IC> {
-    /// Creates a new I2C interface.
+impl<IC> I2cInterface<IC> {
+    /// Creates a new I2C interface with a 7-bit address.
     ///
     /// # Arguments
     ///
     /// * `i2c` - The I2C peripheral.
     /// * `address` - The I2C address of the display.
     pub fn new(i2c: IC, address: u8) -> Self {
-        I2cInterface { i2c, address }
+        Self::with_control_bytes(i2c, address, 0x00, 0x40)
+    }
+
+    /// Creates a new I2C interface with custom control bytes.
+    ///
+    /// The control byte is sent before every command or data transfer. The standard values
+    /// are `0x00` for commands and `0x40` for data, which `new` uses.
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral.
+    /// * `address` - The I2C address of the display.
+    /// * `command_control_byte` - The control byte sent before commands.
+    /// * `data_control_byte` - The control byte sent before display data.
+    pub fn with_control_bytes(
+        i2c: IC,
+        address: u8,
+        command_control_byte: u8,
+        data_control_byte: u8,
+    ) -> Self {
+        Self::with_address(i2c, address, command_control_byte, data_control_byte)
+    }
+}
+
+impl<IC> I2cInterface<IC, TenBitAddress> {
+    /// Creates a new I2C interface with a 10-bit address, for example behind an I2C mux
+    /// using extended addressing.
+    ///
+    /// # Arguments
+    ///
+    /// * `i2c` - The I2C peripheral, which must implement `I2c<TenBitAddress>`.
+    /// * `address` - The 10-bit I2C address of the display.
+    pub fn new_10bit(i2c: IC, address: u16) -> Self {
+        Self::with_address(i2c, address, 0x00, 0x40)
+    }
+}
+
+impl<IC, A: AddressMode> I2cInterface<IC, A> {
+    /// Creates the interface with the defaults shared by every address mode.
+    fn with_address(i2c: IC, address: A, command_control_byte: u8, data_control_byte: u8) -> Self {
+        I2cInterface {
+            i2c,
+            address,
+            command_control_byte,
+            data_control_byte,
+            retries: 0,
+            data_copy: false,
+            max_transfer_bytes: MAX_DATA_BYTES,
+        }
+    }
+}
+
+impl<IC, A> I2cInterface<IC, A> {
+    /// Retries every transfer, including status reads, up to `retries` times when the display
+    /// doesn't acknowledge it.
+    ///
+    /// Some panels NACK the first transactions on a noisy bus or right after power-up, which
+    /// would otherwise fail `init`. Other bus errors are returned right away. Default is `0`.
+    ///
+    /// # Arguments
+    ///
+    /// * `retries` - The number of retries after the first attempt.
+    pub fn with_retries(mut self, retries: u8) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Copies the display data after the control byte into a stack buffer, and sends it with
+    /// a single write operation.
+    ///
+    /// `embedded-hal` requires the adjacent write operations of a transaction to be sent as
+    /// one write, but some HALs restart the transfer between operations or only support a
+    /// single one. Enable this for those, at the cost of a 129 byte copy per transfer.
+    /// Default is `false`.
+    ///
+    /// # Arguments
+    ///
+    /// * `data_copy` - `true` to copy the data into a single write operation.
+    pub fn with_data_copy(mut self, data_copy: bool) -> Self {
+        self.data_copy = data_copy;
+        self
+    }
+
+    /// Limits the display data bytes sent in one I2C transaction.
+    ///
+    /// Some I2C peripherals trip a watchdog or a clock stretching limit on long writes. With
+    /// a limit, `write_data` splits the data into several transactions. The column address
+    /// of the controller advances with every byte, so each transaction continues where the
+    /// previous one stopped without sending the address again. Default is `128`, a full page.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_transfer_bytes` - The data bytes per transaction, clamped to 1-128.
+    pub fn with_max_transfer_bytes(mut self, max_transfer_bytes: usize) -> Self {
+        self.max_transfer_bytes = max_transfer_bytes.clamp(1, MAX_DATA_BYTES);
+        self
+    }
+
+    /// Changes the I2C address of the display, for example after finding it with `scan`.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - The new I2C address of the display.
+    pub fn set_address(&mut self, address: A) {
+        self.address = address;
+    }
+
+    /// Consumes the interface and returns the underlying I2C peripheral.
+    pub fn release(self) -> IC {
+        self.i2c
     }
 }
 
-impl<IC: I2c> CommunicationInterface for I2cInterface<IC> {
+impl<IC: I2c<A>, A: AddressMode + Copy> CommunicationInterface for I2cInterface<IC, A> {
+    const MAX_DATA_BYTES: usize = MAX_DATA_BYTES;
+
     fn init(&mut self) -> Result<(), MiniOledError> {
         Ok(())
     }
 
     fn write_data(&mut self, data_buf: &[u8]) -> Result<(), MiniOledError> {
-        let mut send_buf = [0u8; 130];
-        if data_buf.len() > 128 {
-            return Err(MiniOledError::DataBufferSizeError);
-        }
-        send_buf[0] = 0x40;
-        send_buf[1..data_buf.len() + 1].copy_from_slice(data_buf);
-        self.i2c
-            .write(self.address, &send_buf[..data_buf.len() + 1])
-            .map_err(|e| MiniOledError::I2cError(e.kind()))
+        check_data_size(data_buf)?;
+        data_buf
+            .chunks(self.max_transfer_bytes)
+            .try_for_each(|transfer| self.write_data_transfer(transfer))
     }
 
     fn write_command<const N: usize>(
         &mut self,
         command_buf: &CommandBuffer<N>,
     ) -> Result<(), MiniOledError> {
-        let mut send_buf = [0u8; 30];
-        let command_buf_bytes = command_buf.to_bytes(&mut send_buf[1..])?;
-        let len = command_buf_bytes.len();
+        let mut send_buf = [0u8; COMMAND_FRAME_SIZE];
+        let send_buf = command_frame(self.command_control_byte, command_buf, &mut send_buf)?;
+        self.write_frame(send_buf)
+    }
 
-        self.i2c
-            .write(self.address, &send_buf[..len + 1])
-            .map_err(|e| MiniOledError::I2cError(e.kind()))
+    fn read_status(&mut self) -> Result<u8, MiniOledError> {
+        // The command control byte selects the status register for the read.
+        let control_byte = [self.command_control_byte];
+        let mut status = [0u8];
+        self.run_operations(&mut [
+            Operation::Write(&control_byte),
+            Operation::Read(&mut status),
+        ])?;
+        Ok(status[0])
+    }
+}
+
+impl<IC: I2c<A>, A: AddressMode + Copy> I2cInterface<IC, A> {
+    /// Writes one transaction of display data after the data control byte.
+    fn write_data_transfer(&mut self, data_buf: &[u8]) -> Result<(), MiniOledError> {
+        if self.data_copy {
+            let mut send_buf = [0u8; DATA_FRAME_SIZE];
+            let send_buf = data_frame(self.data_control_byte, data_buf, &mut send_buf)?;
+            return self.write_frame(send_buf);
+        }
+
+        let control_byte = [self.data_control_byte];
+        self.run_operations(&mut [Operation::Write(&control_byte), Operation::Write(data_buf)])
+    }
+
+    /// Writes `frame`, retrying up to `retries` times while it isn't acknowledged.
+    fn write_frame(&mut self, frame: &[u8]) -> Result<(), MiniOledError> {
+        self.run_operations(&mut [Operation::Write(frame)])
+    }
+
+    /// Runs `operations` as one transaction, retrying up to `retries` times while it isn't
+    /// acknowledged.
+    fn run_operations(&mut self, operations: &mut [Operation<'_>]) -> Result<(), MiniOledError> {
+        let mut attempts = 0;
+        loop {
+            match self.i2c.transaction(self.address, operations) {
+                Ok(()) => return Ok(()),
+                Err(e) if is_nack(e.kind()) && attempts < self.retries => attempts += 1,
+                Err(e) => return Err(MiniOledError::I2cError(e.kind())),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<IC: embedded_hal_async::i2c::I2c<A>, A: AddressMode + Copy> AsyncCommunicationInterface
+    for I2cInterface<IC, A>
+{
+    const MAX_DATA_BYTES: usize = MAX_DATA_BYTES;
+
+    async fn init(&mut self) -> Result<(), MiniOledError> {
+        Ok(())
+    }
+
+    async fn write_data(&mut self, data_buf: &[u8]) -> Result<(), MiniOledError> {
+        check_data_size(data_buf)?;
+        for transfer in data_buf.chunks(self.max_transfer_bytes) {
+            self.write_data_transfer_async(transfer).await?;
+        }
+        Ok(())
+    }
+
+    async fn write_command<const N: usize>(
+        &mut self,
+        command_buf: &CommandBuffer<N>,
+    ) -> Result<(), MiniOledError> {
+        let mut send_buf = [0u8; COMMAND_FRAME_SIZE];
+        let send_buf = command_frame(self.command_control_byte, command_buf, &mut send_buf)?;
+        self.write_frame_async(send_buf).await
+    }
+
+    async fn read_status(&mut self) -> Result<u8, MiniOledError> {
+        let control_byte = [self.command_control_byte];
+        let mut status = [0u8];
+        self.run_operations_async(&mut [
+            Operation::Write(&control_byte),
+            Operation::Read(&mut status),
+        ])
+        .await?;
+        Ok(status[0])
+    }
+}
+
+#[cfg(feature = "async")]
+impl<IC: embedded_hal_async::i2c::I2c<A>, A: AddressMode + Copy> I2cInterface<IC, A> {
+    /// Asynchronously writes one transaction of display data after the data control byte.
+    async fn write_data_transfer_async(&mut self, data_buf: &[u8]) -> Result<(), MiniOledError> {
+        if self.data_copy {
+            let mut send_buf = [0u8; DATA_FRAME_SIZE];
+            let send_buf = data_frame(self.data_control_byte, data_buf, &mut send_buf)?;
+            return self.write_frame_async(send_buf).await;
+        }
+
+        let control_byte = [self.data_control_byte];
+        self.run_operations_async(&mut [
+            Operation::Write(&control_byte),
+            Operation::Write(data_buf),
+        ])
+        .await
+    }
+
+    /// Asynchronously writes `frame`, retrying up to `retries` times while it isn't
+    /// acknowledged.
+    async fn write_frame_async(&mut self, frame: &[u8]) -> Result<(), MiniOledError> {
+        self.run_operations_async(&mut [Operation::Write(frame)])
+            .await
+    }
+
+    /// Asynchronously runs `operations` as one transaction, retrying up to `retries` times
+    /// while it isn't acknowledged.
+    async fn run_operations_async(
+        &mut self,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), MiniOledError> {
+        let mut attempts = 0;
+        loop {
+            match self.i2c.transaction(self.address, operations).await {
+                Ok(()) => return Ok(()),
+                Err(e) if is_nack(e.kind()) && attempts < self.retries => attempts += 1,
+                Err(e) => return Err(MiniOledError::I2cError(e.kind())),
+            }
+        }
+    }
+}
+
+fn is_nack(kind: ErrorKind) -> bool {
+    matches!(kind, ErrorKind::NoAcknowledge(_))
+}
+
+/// Returns the first of `addresses` where a display acknowledges a NOP command.
+///
+/// Modules ship at either `0x3C` or `0x3D` depending on a solder jumper, so this finds the
+/// right one when it isn't known. The NOP doesn't change the state of the display.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use mini_oled::interface::i2c::{I2cInterface, scan};
+///
+/// // let mut i2c = ...;
+/// let address = scan(&mut i2c, &[0x3C, 0x3D]).expect("no display found");
+/// let interface = I2cInterface::new(i2c, address);
+/// ```
+///
+/// # Arguments
+///
+/// * `i2c` - The I2C peripheral.
+/// * `addresses` - The 7-bit addresses to probe, in order.
+///
+/// # Returns
+///
+/// The first address that acknowledged, or `None` if none did.
+pub fn scan<IC: I2c>(i2c: &mut IC, addresses: &[u8]) -> Option<u8> {
+    let mut send_buf = [0u8; COMMAND_FRAME_SIZE];
+    let send_buf = command_frame(0x00, &CommandBuffer::from(Command::Noop), &mut send_buf).ok()?;
+
+    addresses
+        .iter()
+        .copied()
+        .find(|&address| i2c.write(address, send_buf).is_ok())
+}
+
+/// Prepends the data control byte to `data_buf` inside `send_buf`.
+fn data_frame<'a>(
+    control_byte: u8,
+    data_buf: &[u8],
+    send_buf: &'a mut [u8; DATA_FRAME_SIZE],
+) -> Result<&'a [u8], MiniOledError> {
+    check_data_size(data_buf)?;
+    send_buf[0] = control_byte;
+    send_buf[1..data_buf.len() + 1].copy_from_slice(data_buf);
+    Ok(&send_buf[..data_buf.len() + 1])
+}
+
+/// Rejects display data longer than one transfer.
+fn check_data_size(data_buf: &[u8]) -> Result<(), MiniOledError> {
+    match data_buf.len() > MAX_DATA_BYTES {
+        true => Err(MiniOledError::DataBufferSizeError),
+        false => Ok(()),
     }
 }
+
+/// Serializes `command_buf` after the command control byte inside `send_buf`.
+fn command_frame<'a, const N: usize>(
+    control_byte: u8,
+    command_buf: &CommandBuffer<N>,
+    send_buf: &'a mut [u8; COMMAND_FRAME_SIZE],
+) -> Result<&'a [u8], MiniOledError> {
+    // `to_bytes` leaves the first byte free for the control byte.
+    let len = command_buf.to_bytes(send_buf)?.len();
+    send_buf[0] = control_byte;
+    Ok(&send_buf[..len])
+}
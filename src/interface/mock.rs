@@ -0,0 +1,102 @@
+//! # Mock Interface
+//!
+//! A `CommunicationInterface` that records everything the driver sends instead of talking to
+//! hardware. It is available with the `mock` feature and is meant for testing init sequences
+//! and flush logic.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use mini_oled::{command::Command, interface::mock::MockInterface, screen::sh1106::Sh1106};
+//!
+//! let mut screen = Sh1106::new(MockInterface::<32, 1024>::new());
+//! screen.init().unwrap();
+//!
+//! let mock = screen.release();
+//! assert_eq!(mock.commands().last(), Some(&Command::TurnDisplayOn));
+//! ```
+
+use heapless::Vec;
+
+use crate::{
+    command::{Command, CommandBuffer},
+    error::MiniOledError,
+};
+
+use super::CommunicationInterface;
+
+/// Recording communication interface.
+///
+/// Keeps up to `C` commands and `D` data bytes. Writing more than that returns
+/// `MiniOledError::CommandBufferSizeError` or `MiniOledError::DataBufferSizeError`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use mini_oled::interface::mock::MockInterface;
+///
+/// let interface: MockInterface<32, 1024> = MockInterface::new();
+/// ```
+#[derive(Debug, Default)]
+pub struct MockInterface<const C: usize, const D: usize> {
+    commands: Vec<Command, C>,
+    data: Vec<u8, D>,
+    data_writes: usize,
+}
+
+impl<const C: usize, const D: usize> MockInterface<C, D> {
+    /// Creates a new, empty mock interface.
+    pub fn new() -> Self {
+        MockInterface {
+            commands: Vec::new(),
+            data: Vec::new(),
+            data_writes: 0,
+        }
+    }
+
+    /// Returns every command written so far, in order.
+    pub fn commands(&self) -> &[Command] {
+        &self.commands
+    }
+
+    /// Returns every data byte written so far, in order.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Returns the number of `write_data` calls so far.
+    pub fn data_writes(&self) -> usize {
+        self.data_writes
+    }
+
+    /// Forgets everything recorded so far.
+    pub fn clear(&mut self) {
+        self.commands.clear();
+        self.data.clear();
+        self.data_writes = 0;
+    }
+}
+
+impl<const C: usize, const D: usize> CommunicationInterface for MockInterface<C, D> {
+    fn init(&mut self) -> Result<(), MiniOledError> {
+        Ok(())
+    }
+
+    fn write_command<const N: usize>(
+        &mut self,
+        buf: &CommandBuffer<N>,
+    ) -> Result<(), MiniOledError> {
+        buf.validate()?;
+        self.commands
+            .extend_from_slice(buf.as_slice())
+            .map_err(|_| MiniOledError::CommandBufferSizeError)
+    }
+
+    fn write_data(&mut self, buf: &[u8]) -> Result<(), MiniOledError> {
+        self.data
+            .extend_from_slice(buf)
+            .map_err(|_| MiniOledError::DataBufferSizeError)?;
+        self.data_writes += 1;
+        Ok(())
+    }
+}
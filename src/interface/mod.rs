@@ -1,6 +1,7 @@
 //! # Communication Interface
 //!
-//! This module defines the `CommunicationInterface` trait and provides implementations for I2C and ~~SPI~~ (planned).
+//! This module defines the `CommunicationInterface` trait and provides implementations for I2C and,
+//! with the `spi` feature (enabled by default), 4-wire and 3-wire SPI.
 //! It abstracts the underlying hardware communication details.
 //!
 //! ## Example
@@ -17,12 +18,23 @@
 use crate::{command::CommandBuffer, error::MiniOledError};
 
 pub mod i2c;
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
+#[cfg(feature = "spi")]
 pub mod spi;
 
+/// The largest number of command bytes the built-in interfaces send with one `write_command`.
+pub const MAX_COMMAND_BYTES: usize = 29;
+
 /// Trait representing the communication interface with the display.
 ///
-/// This trait is implemented by `I2cInterface` and `SPIInterface`.
+/// This trait is implemented by `I2cInterface`, `SpiInterface` and, with the `mock` feature, `MockInterface`.
 pub trait CommunicationInterface {
+    /// The largest number of bytes a single `write_data` call accepts.
+    ///
+    /// The driver splits longer transfers into several calls. Unlimited by default.
+    const MAX_DATA_BYTES: usize = usize::MAX;
+
     /// Initialize the communication device.
     ///
     /// # Returns
@@ -54,4 +66,71 @@ pub trait CommunicationInterface {
     ///
     /// `Ok(())` on success, or a `MiniOledError` on failure.
     fn write_data(&mut self, buf: &[u8]) -> Result<(), MiniOledError>;
+
+    /// Read the status byte of the device.
+    ///
+    /// Bit 7 is set while the controller is busy and bit 6 while the display is off.
+    /// Write-only interfaces, like SPI, keep the default implementation.
+    ///
+    /// # Returns
+    ///
+    /// The status byte on success, or `MiniOledError::UnsupportedOperation` if the interface
+    /// can't read from the device.
+    fn read_status(&mut self) -> Result<u8, MiniOledError> {
+        Err(MiniOledError::UnsupportedOperation)
+    }
+}
+
+/// Asynchronous version of `CommunicationInterface`, backed by `embedded-hal-async`.
+///
+/// This trait is implemented by `I2cInterface` and `SpiInterface` when the `async` feature is enabled.
+#[cfg(feature = "async")]
+#[allow(async_fn_in_trait)]
+pub trait AsyncCommunicationInterface {
+    /// The largest number of bytes a single `write_data` call accepts.
+    ///
+    /// The driver splits longer transfers into several calls. Unlimited by default.
+    const MAX_DATA_BYTES: usize = usize::MAX;
+
+    /// Initialize the communication device.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` on success, or a `MiniOledError` on failure.
+    async fn init(&mut self) -> Result<(), MiniOledError>;
+
+    /// Send a command buffer to the device.
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The command buffer to send.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` on success, or a `MiniOledError` on failure.
+    async fn write_command<const N: usize>(
+        &mut self,
+        buf: &CommandBuffer<N>,
+    ) -> Result<(), MiniOledError>;
+
+    /// Send data to the device.
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The data buffer to send.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` on success, or a `MiniOledError` on failure.
+    async fn write_data(&mut self, buf: &[u8]) -> Result<(), MiniOledError>;
+
+    /// Read the status byte of the device, see `CommunicationInterface::read_status`.
+    ///
+    /// # Returns
+    ///
+    /// The status byte on success, or `MiniOledError::UnsupportedOperation` if the interface
+    /// can't read from the device.
+    async fn read_status(&mut self) -> Result<u8, MiniOledError> {
+        Err(MiniOledError::UnsupportedOperation)
+    }
 }
@@ -1,51 +1,208 @@
-use embedded_hal::spi::SpiBus;
+use embedded_hal::{
+    digital::{self, OutputPin},
+    spi::{self, SpiDevice},
+};
 
 use crate::{command::CommandBuffer, error::MiniOledError};
 
-use super::CommunicationInterface;
+use super::{CommunicationInterface, MAX_COMMAND_BYTES};
 
-/// SPI communication interface.
+#[cfg(feature = "async")]
+use super::AsyncCommunicationInterface;
+
+/// Size of the buffer used by `write_command`, one unused byte and up to `MAX_COMMAND_BYTES`
+/// command bytes.
+const COMMAND_BUFFER_SIZE: usize = MAX_COMMAND_BYTES + 1;
+
+/// The largest number of 9-bit words sent by one transaction of the 3-wire interface.
+const MAX_3WIRE_WORDS: usize = 32;
+
+/// D/C bit of a 9-bit word sent by the 3-wire interface, set for display data.
+const DATA_WORD_BIT: u16 = 0x100;
+
+/// 4-wire or 3-wire SPI communication interface.
 ///
-/// This struct implements the `CommunicationInterface` trait for SPI.
+/// The D/C pin selects between commands (low) and display data (high). Modules without a
+/// D/C line use `SpiInterface::new_3wire` instead, which sends the D/C bit in 9-bit words. The chip select line
+/// is handled by the `SpiDevice`, for example `embedded_hal_bus::spi::ExclusiveDevice`.
+///
+/// Bus failures are reported as `MiniOledError::SpiBusError` and D/C pin failures as
+/// `MiniOledError::PinError`.
 ///
 /// # Example
 ///
 /// ```rust,ignore
 /// use mini_oled::interface::spi::SpiInterface;
 ///
-/// // Verify that your SPI driver implements embedded_hal::spi::SpiBus
-/// // let spi_driver = ...;
-/// let interface = SpiInterface::new(spi_driver);
+/// // Verify that your SPI driver implements embedded_hal::spi::SpiDevice
+/// // let spi_device = ...;
+/// // let dc_pin = ...;
+/// let interface = SpiInterface::new(spi_device, dc_pin);
 /// ```
-pub struct SpiInterface<SB: SpiBus> {
-    _spi_bus: SB,
+pub struct SpiInterface<SPI, DC> {
+    spi: SPI,
+    dc: DC,
 }
 
-impl<SB: SpiBus> SpiInterface<SB> {
+impl<SPI, DC> SpiInterface<SPI, DC> {
     /// Creates a new SPI interface.
     ///
     /// # Arguments
     ///
-    /// * `_spi_bus` - The SPI bus.
-    #[allow(unused)]
-    pub fn new(_spi_bus: SB) -> Self {
-        Self { _spi_bus }
+    /// * `spi` - The SPI device.
+    /// * `dc` - The output pin connected to the D/C line of the display.
+    pub fn new(spi: SPI, dc: DC) -> Self {
+        Self { spi, dc }
+    }
+
+    /// Consumes the interface and returns the underlying SPI device and D/C pin.
+    pub fn release(self) -> (SPI, DC) {
+        (self.spi, self.dc)
+    }
+}
+
+/// Placeholder for the D/C pin of a 3-wire `SpiInterface`, created by
+/// `SpiInterface::new_3wire`.
+#[derive(Debug, Clone, Copy)]
+pub struct ThreeWire;
+
+impl<SPI> SpiInterface<SPI, ThreeWire> {
+    /// Creates a new 3-wire SPI interface, for modules without a D/C line.
+    ///
+    /// Every byte is sent as a 9-bit word, with the D/C bit in front of it: `0` for commands
+    /// and `1` for display data. The bus must be configured for 9-bit frames, and the
+    /// `SpiDevice` takes the words as `u16` with the D/C bit in bit 8.
+    ///
+    /// # Arguments
+    ///
+    /// * `spi` - The SPI device, configured for 9-bit words.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use mini_oled::interface::spi::SpiInterface;
+    ///
+    /// // let spi_device = ...; // implements SpiDevice<u16> with 9-bit frames
+    /// let interface = SpiInterface::new_3wire(spi_device);
+    /// ```
+    pub fn new_3wire(spi: SPI) -> Self {
+        Self { spi, dc: ThreeWire }
     }
 }
 
-impl<SB: SpiBus> CommunicationInterface for SpiInterface<SB> {
+impl<SPI: SpiDevice<u16>> CommunicationInterface for SpiInterface<SPI, ThreeWire> {
     fn init(&mut self) -> Result<(), MiniOledError> {
         Ok(())
     }
 
-    fn write_data(&mut self, _buf: &[u8]) -> Result<(), MiniOledError> {
-        todo!()
+    fn write_data(&mut self, data_buf: &[u8]) -> Result<(), MiniOledError> {
+        for chunk in data_buf.chunks(MAX_3WIRE_WORDS) {
+            let mut words = [0u16; MAX_3WIRE_WORDS];
+            let words = to_words(chunk, DATA_WORD_BIT, &mut words);
+            self.spi.write(words).map_err(bus_error)?;
+        }
+        Ok(())
     }
 
     fn write_command<const N: usize>(
         &mut self,
-        _buf: &CommandBuffer<N>,
+        command_buf: &CommandBuffer<N>,
     ) -> Result<(), MiniOledError> {
-        todo!()
+        let mut send_buf = [0u8; COMMAND_BUFFER_SIZE];
+        let send_buf = &command_buf.to_bytes(&mut send_buf)?[1..];
+        let mut words = [0u16; COMMAND_BUFFER_SIZE];
+        let words = to_words(send_buf, 0, &mut words);
+        self.spi.write(words).map_err(bus_error)
     }
 }
+
+impl<SPI: SpiDevice, DC: OutputPin> CommunicationInterface for SpiInterface<SPI, DC> {
+    fn init(&mut self) -> Result<(), MiniOledError> {
+        Ok(())
+    }
+
+    fn write_data(&mut self, data_buf: &[u8]) -> Result<(), MiniOledError> {
+        self.dc.set_high().map_err(pin_error)?;
+        self.spi.write(data_buf).map_err(bus_error)
+    }
+
+    fn write_command<const N: usize>(
+        &mut self,
+        command_buf: &CommandBuffer<N>,
+    ) -> Result<(), MiniOledError> {
+        let mut send_buf = [0u8; COMMAND_BUFFER_SIZE];
+        // `to_bytes` leaves the first byte free for a control byte, which SPI doesn't use.
+        let send_buf = &command_buf.to_bytes(&mut send_buf)?[1..];
+        self.dc.set_low().map_err(pin_error)?;
+        self.spi.write(send_buf).map_err(bus_error)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<SPI: embedded_hal_async::spi::SpiDevice, DC: OutputPin> AsyncCommunicationInterface
+    for SpiInterface<SPI, DC>
+{
+    async fn init(&mut self) -> Result<(), MiniOledError> {
+        Ok(())
+    }
+
+    async fn write_data(&mut self, data_buf: &[u8]) -> Result<(), MiniOledError> {
+        self.dc.set_high().map_err(pin_error)?;
+        self.spi.write(data_buf).await.map_err(bus_error)
+    }
+
+    async fn write_command<const N: usize>(
+        &mut self,
+        command_buf: &CommandBuffer<N>,
+    ) -> Result<(), MiniOledError> {
+        let mut send_buf = [0u8; COMMAND_BUFFER_SIZE];
+        let send_buf = &command_buf.to_bytes(&mut send_buf)?[1..];
+        self.dc.set_low().map_err(pin_error)?;
+        self.spi.write(send_buf).await.map_err(bus_error)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<SPI: embedded_hal_async::spi::SpiDevice<u16>> AsyncCommunicationInterface
+    for SpiInterface<SPI, ThreeWire>
+{
+    async fn init(&mut self) -> Result<(), MiniOledError> {
+        Ok(())
+    }
+
+    async fn write_data(&mut self, data_buf: &[u8]) -> Result<(), MiniOledError> {
+        for chunk in data_buf.chunks(MAX_3WIRE_WORDS) {
+            let mut words = [0u16; MAX_3WIRE_WORDS];
+            let words = to_words(chunk, DATA_WORD_BIT, &mut words);
+            self.spi.write(words).await.map_err(bus_error)?;
+        }
+        Ok(())
+    }
+
+    async fn write_command<const N: usize>(
+        &mut self,
+        command_buf: &CommandBuffer<N>,
+    ) -> Result<(), MiniOledError> {
+        let mut send_buf = [0u8; COMMAND_BUFFER_SIZE];
+        let send_buf = &command_buf.to_bytes(&mut send_buf)?[1..];
+        let mut words = [0u16; COMMAND_BUFFER_SIZE];
+        let words = to_words(send_buf, 0, &mut words);
+        self.spi.write(words).await.map_err(bus_error)
+    }
+}
+
+/// Widens `bytes` into 9-bit words with `dc_bit`, in the front of `words`.
+fn to_words<'a>(bytes: &[u8], dc_bit: u16, words: &'a mut [u16]) -> &'a [u16] {
+    for (word, &byte) in words.iter_mut().zip(bytes) {
+        *word = dc_bit | byte as u16;
+    }
+    &words[..bytes.len()]
+}
+
+fn bus_error<E: spi::Error>(error: E) -> MiniOledError {
+    MiniOledError::from(error.kind())
+}
+
+fn pin_error<E: digital::Error>(error: E) -> MiniOledError {
+    MiniOledError::from(error.kind())
+}
@@ -1,7 +1,7 @@
 #![no_std]
 //! # Mini OLED
 //!
-//! `mini-oled` is an I2C/~~SPI~~ driver for the SH1106 OLED display controller, designed for embedded no-std environments.
+//! `mini-oled` is an I2C/SPI driver for the SH1106 OLED display controller, designed for embedded no-std environments.
 //! It supports basic drawing operations and integrates with `embedded-graphics` for advanced graphics.
 //!
 //! ## Usage
@@ -50,6 +50,9 @@
 //!     .background_color(BinaryColor::Off)
 //!     .build();
 //!
+//! // let mut delay = ...; // Your DelayNs provider
+//! let timer = FrameTimer::new(30);
+//!
 //! let mut i = 0;
 //! let mut old_i = 0;
 //!
@@ -83,8 +86,8 @@
 //!     .draw(screen.get_mut_canvas())
 //!     .unwrap();
 //!
-//!     // 4. Send changes to the display
-//!     screen.flush().unwrap();
+//!     // 4. Send changes to the display and wait for the next frame
+//!     screen.flush_paced(&mut delay, &timer).unwrap();
 //! }
 //! ```
 //!
@@ -113,8 +116,16 @@
 //!
 //! // IMPORTANT: Changing the buffer directly does NOT update the "dirty area".
 //! // The driver does not know which pixels changed.
-//! // You must use `flush_all()` to send the entire buffer to the display.
+//! // Either tell it with `mark_dirty()` and use `flush()`...
+//! screen.get_mut_canvas().mark_dirty((0, 0), (7, 7));
+//! screen.flush().unwrap();
+//!
+//! // ...or use `flush_all()` to send the entire buffer to the display.
 //! screen.flush_all().unwrap();
+//!
+//! // Edits through `buffer_guard()` mark the whole buffer dirty when the guard is dropped.
+//! screen.get_mut_canvas().buffer_guard()[0] = 0xFF;
+//! screen.flush().unwrap();
 //! ```
 
 pub mod command;
@@ -123,4 +134,7 @@ pub mod interface;
 pub mod prelude;
 pub mod screen;
 
+#[cfg(test)]
+extern crate std;
+#[cfg(test)]
 mod tests;
@@ -10,6 +10,13 @@
 
 pub use crate::error::MiniOledError;
 pub use crate::interface::i2c::I2cInterface;
+#[cfg(feature = "spi")]
 pub use crate::interface::spi::SpiInterface;
-pub use crate::screen::properties::{DisplayProperties, DisplayRotation};
+pub use crate::screen::animator::DisplayStartLineAnimator;
+pub use crate::screen::builder::Sh1106Builder;
+pub use crate::screen::properties::{
+    BlitMode, ComPinConfig, Controller, DisplayProperties, DisplayRotation, DitherMode,
+    RotationMode, TestPattern,
+};
 pub use crate::screen::sh1106::Sh1106;
+pub use crate::screen::timer::FrameTimer;
@@ -0,0 +1,110 @@
+//! # Animator
+//!
+//! This module contains the `DisplayStartLineAnimator`, which scrolls the display vertically
+//! by moving the display start line, without the continuous scroll commands.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use mini_oled::screen::{animator::DisplayStartLineAnimator, timer::FrameTimer};
+//!
+//! // let mut delay = ...; // Your DelayNs provider
+//! let timer = FrameTimer::new(30);
+//! let mut animator = DisplayStartLineAnimator::new(1);
+//!
+//! loop {
+//!     animator.step(&mut screen).unwrap();
+//!     screen.flush_paced(&mut delay, &timer).unwrap();
+//! }
+//! ```
+
+use crate::{error::MiniOledError, interface::CommunicationInterface, screen::sh1106::Sh1106};
+
+/// The number of RAM rows, which the start line wraps around.
+const RAM_ROWS: u8 = 64;
+
+/// Vertical marquee driven by the display start line.
+///
+/// Every `step` moves the start line by a fixed number of rows, so the RAM content moves up
+/// and the rows leaving the top come back at the bottom. Only one command is sent per step,
+/// the RAM isn't rewritten. This works on every SH1106 and SSD1306, including clones whose
+/// continuous scroll is missing or buggy.
+///
+/// The buffer can still be changed and flushed between the steps, for example to replace
+/// the rows that are hidden at the moment. The start line is in RAM rows, so the content
+/// moves along the COM scan direction of the panel whatever the rotation of the canvas.
+///
+/// # Example
+///
+/// ```rust
+/// use mini_oled::screen::animator::DisplayStartLineAnimator;
+///
+/// let animator = DisplayStartLineAnimator::new(2);
+/// assert_eq!(animator.line(), 0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisplayStartLineAnimator {
+    line: u8,
+    rows_per_step: u8,
+}
+
+impl DisplayStartLineAnimator {
+    /// Creates an animator starting at line 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `rows_per_step` - The rows moved by every `step`, wrapped to 0-63. `64 - n` moves
+    ///   the content the other way by `n` rows.
+    pub const fn new(rows_per_step: u8) -> Self {
+        DisplayStartLineAnimator {
+            line: 0,
+            rows_per_step: rows_per_step % RAM_ROWS,
+        }
+    }
+
+    /// Returns the current start line, the RAM row shown at the top of the display.
+    pub const fn line(&self) -> u8 {
+        self.line
+    }
+
+    /// Advances the start line by `rows_per_step` rows and sends it to the display.
+    ///
+    /// # Arguments
+    ///
+    /// * `screen` - The display to animate.
+    pub fn step<
+        CI: CommunicationInterface,
+        const N: usize,
+        const W: u32,
+        const H: u32,
+        const O: u8,
+    >(
+        &mut self,
+        screen: &mut Sh1106<CI, N, W, H, O>,
+    ) -> Result<(), MiniOledError> {
+        let line = (self.line + self.rows_per_step) % RAM_ROWS;
+        screen.set_start_line(line)?;
+        self.line = line;
+        Ok(())
+    }
+
+    /// Moves the start line back to 0 and sends it to the display.
+    ///
+    /// # Arguments
+    ///
+    /// * `screen` - The display to animate.
+    pub fn reset<
+        CI: CommunicationInterface,
+        const N: usize,
+        const W: u32,
+        const H: u32,
+        const O: u8,
+    >(
+        &mut self,
+        screen: &mut Sh1106<CI, N, W, H, O>,
+    ) -> Result<(), MiniOledError> {
+        screen.set_start_line(0)?;
+        self.line = 0;
+        Ok(())
+    }
+}
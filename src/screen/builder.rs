@@ -0,0 +1,229 @@
+//! # Builder
+//!
+//! This module contains the `Sh1106Builder`, which allows overriding the settings that are
+//! sent to the display by `Sh1106::init`. Any setting that is not overridden falls back to
+//! the same default used by `Sh1106::new`.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use mini_oled::{
+//!     interface::i2c::I2cInterface,
+//!     screen::{builder::Sh1106Builder, properties::ComPinConfig},
+//! };
+//!
+//! // let i2c = ...; // Your I2C driver
+//! let i2c_interface = I2cInterface::new(i2c, 0x3C);
+//! let mut screen = Sh1106Builder::new()
+//!     .contrast(0x40)
+//!     .com_pin_config(ComPinConfig::Sequential)
+//!     .charge_pump(false)
+//!     .build(i2c_interface);
+//!
+//! screen.init().unwrap();
+//! ```
+
+use crate::{
+    command::{Command, VcomhLevel},
+    screen::{
+        properties::{ComPinConfig, Controller},
+        sh1106::Sh1106,
+    },
+};
+
+/// Settings sent to the display by the initialization sequence.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct InitConfig {
+    contrast: u8,
+    com_pin_config: ComPinConfig,
+    precharge_period: (u8, u8),
+    display_clock_div: (u8, u8),
+    charge_pump: bool,
+    controller: Controller,
+    column_offset: Option<u8>,
+}
+
+impl InitConfig {
+    pub(crate) fn get_controller(&self) -> Controller {
+        self.controller
+    }
+
+    pub(crate) fn get_column_offset(&self) -> Option<u8> {
+        self.column_offset
+    }
+
+    pub(crate) fn get_contrast(&self) -> u8 {
+        self.contrast
+    }
+
+    /// Returns the charge pump command of the controller, which only enables the charge pump
+    /// if `enabled` and the configuration uses it.
+    pub(crate) fn charge_pump_command(&self, enabled: bool) -> Command {
+        match (self.controller, enabled && self.charge_pump) {
+            (Controller::Sh1106, true) => Command::EnableChargePump,
+            (Controller::Sh1106, false) => Command::DisableChargePump,
+            (Controller::Ssd1306, true) => Command::EnableSsd1306ChargePump,
+            (Controller::Ssd1306, false) => Command::DisableSsd1306ChargePump,
+        }
+    }
+
+    pub(crate) fn init_sequence(
+        &self,
+        multiplex: u8,
+        [segment_remap, com_scan_direction]: [Command; 2],
+    ) -> [Command; 15] {
+        [
+            Command::TurnDisplayOff,
+            Command::DisplayClockDiv(self.display_clock_div.0, self.display_clock_div.1),
+            Command::Multiplex(multiplex),
+            Command::DisplayOffset(0),
+            Command::StartLine(0),
+            self.charge_pump_command(true),
+            segment_remap,
+            com_scan_direction,
+            match self.com_pin_config {
+                ComPinConfig::Sequential => Command::SequentialComPinConfig,
+                ComPinConfig::Alternative => Command::AlternativeComPinConfig,
+            },
+            Command::Contrast(self.contrast),
+            Command::PreChargePeriod(self.precharge_period.0, self.precharge_period.1),
+            Command::VcomhDeselect(VcomhLevel::Auto),
+            Command::DisableTestScreen,
+            Command::PositiveImageMode,
+            Command::TurnDisplayOn,
+        ]
+    }
+}
+
+impl Default for InitConfig {
+    fn default() -> Self {
+        Self {
+            contrast: 0x80,
+            com_pin_config: ComPinConfig::Alternative,
+            precharge_period: (0x1, 0xF),
+            display_clock_div: (0x8, 0x0),
+            charge_pump: true,
+            controller: Controller::Sh1106,
+            column_offset: None,
+        }
+    }
+}
+
+/// Builder for an `Sh1106` driver with a custom initialization sequence.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use mini_oled::screen::builder::Sh1106Builder;
+///
+/// // let i2c_interface = ...;
+/// let mut screen = Sh1106Builder::new()
+///     .precharge_period(0x2, 0x2)
+///     .build(i2c_interface);
+/// screen.init().unwrap();
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Sh1106Builder {
+    init_config: InitConfig,
+}
+
+impl Sh1106Builder {
+    /// Creates a new builder with the default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the initial contrast. Default is `0x80`.
+    ///
+    /// It is sent inside the `init` sequence, so a dim display never flashes at the default
+    /// level and needs no extra `set_contrast` write.
+    pub fn contrast(mut self, contrast: u8) -> Self {
+        self.init_config.contrast = contrast;
+        self
+    }
+
+    /// Sets the COM pin hardware configuration. Default is `ComPinConfig::Alternative`.
+    pub fn com_pin_config(mut self, com_pin_config: ComPinConfig) -> Self {
+        self.init_config.com_pin_config = com_pin_config;
+        self
+    }
+
+    /// Sets phase 1 and 2 of the precharge period. Each value is from 0-15.
+    /// Default is `(0x1, 0xF)`.
+    pub fn precharge_period(mut self, phase1: u8, phase2: u8) -> Self {
+        self.init_config.precharge_period = (phase1, phase2);
+        self
+    }
+
+    /// Sets the display clock.
+    /// First value is oscillator frequency, second value is divide ratio - 1.
+    /// Default is `(0x8, 0x0)`.
+    pub fn display_clock_div(mut self, fosc: u8, div: u8) -> Self {
+        self.init_config.display_clock_div = (fosc, div);
+        self
+    }
+
+    /// Enables or disables the internal charge pump.
+    /// Disable it when the panel is powered externally. Default is `true`.
+    pub fn charge_pump(mut self, enabled: bool) -> Self {
+        self.init_config.charge_pump = enabled;
+        self
+    }
+
+    /// Sets the display controller of the module. Default is `Controller::Sh1106`.
+    ///
+    /// `Controller::Ssd1306` removes the two column offset of the SH1106 and uses the SSD1306
+    /// charge pump command. That fits 128 column panels, other panels may need the offset set
+    /// with `column_offset`. Use `build_with_size` for SSD1306 panels that aren't 128x64.
+    pub fn controller(mut self, controller: Controller) -> Self {
+        self.init_config.controller = controller;
+        self
+    }
+
+    /// Sets the RAM column of the leftmost panel column, replacing the one derived from the
+    /// offset `O` of the driver type and the controller.
+    ///
+    /// For example 64x48 SSD1306 modules start at column 32, like the SH1106 ones, where the
+    /// derived offset would be 30. Columns past the 132 of the controller RAM make `flush`
+    /// fail with `MiniOledError::InvalidCommandArgument`.
+    pub fn column_offset(mut self, column_offset: u8) -> Self {
+        self.init_config.column_offset = Some(column_offset);
+        self
+    }
+
+    /// Creates the `Sh1106` driver with the configured settings.
+    ///
+    /// # Arguments
+    ///
+    /// * `communication_interface` - The initialized communication interface (I2C or SPI).
+    pub fn build<CI>(self, communication_interface: CI) -> Sh1106<CI> {
+        Sh1106::with_init_config(communication_interface, self.init_config)
+    }
+
+    /// Creates an `Sh1106` driver for a panel of any size with the configured settings.
+    ///
+    /// The size is given by the const generic parameters and checked at compile time, like
+    /// with `Sh1106::new_with_size`. Smaller panels often need `ComPinConfig::Sequential` or
+    /// another precharge period, which `build` can't apply since it is fixed to 128x64.
+    ///
+    /// # Arguments
+    ///
+    /// * `communication_interface` - The initialized communication interface (I2C or SPI).
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use mini_oled::screen::{builder::Sh1106Builder, properties::ComPinConfig, sh1106::Sh1106};
+    ///
+    /// // A 128x32 panel
+    /// let mut screen: Sh1106<_, 512, 128, 32, 2> = Sh1106Builder::new()
+    ///     .com_pin_config(ComPinConfig::Sequential)
+    ///     .build_with_size(i2c_interface);
+    /// ```
+    pub fn build_with_size<CI, const N: usize, const W: u32, const H: u32, const O: u8>(
+        self,
+        communication_interface: CI,
+    ) -> Sh1106<CI, N, W, H, O> {
+        Sh1106::with_init_config(communication_interface, self.init_config)
+    }
+}
@@ -8,18 +8,45 @@
 //!
 //! ```rust,ignore
 //! use mini_oled::screen::canvas::Canvas;
-//! // Canvas is normally obtained from the display driver, not created directly.
+//! // Canvas is normally obtained from the display driver.
 //! // let canvas = display.get_mut_canvas();
 //!
+//! // A standalone canvas renders off-screen, without any interface.
+//! // let canvas: Canvas<1024, 128, 64, 2> = Canvas::default();
+//!
 //! // set_pixel(x, y, on/off)
 //! // canvas.set_pixel(10, 20, true);
 //! ```
+//!
+//! ## Rotation
+//!
+//! By default (`RotationMode::PerPixel`) the buffer uses the controller layout. In 90 and 270
+//! degree rotations the logical coordinates are swapped when a pixel is written, so `flush`
+//! sends the buffer as is, but a horizontal line is drawn a pixel at a time.
+//!
+//! `Canvas::set_rotation_mode` with `RotationMode::FlushTime` stores the logical layout in
+//! these rotations instead and transposes every page it sends. Dirty tracking, `fill_page` and
+//! the page runs of `flush` keep using the controller layout, only `get_buffer` and
+//! `get_mut_buffer` see the logical one.
+//!
+//! Drawing horizontal lines, text and fills gets cheaper, as whole bytes are written instead
+//! of single bits, while every flush gets more expensive, as each sent byte is gathered from 8
+//! buffer bytes. `FlushTime` pays off when drawing a lot and flushing small dirty areas.
+
+use core::ops::{Deref, DerefMut};
 
 use crate::screen::fast_mul;
 
+use crate::command::{Command, CommandBuffer, Page};
 use crate::error::MiniOledError;
+use crate::interface::CommunicationInterface;
 
-use crate::screen::properties::{DisplayProperties, DisplayRotation};
+#[cfg(feature = "async")]
+use crate::interface::AsyncCommunicationInterface;
+
+use crate::screen::properties::{
+    BlitMode, Controller, DisplayProperties, DisplayRotation, DitherMode, RotationMode, TestPattern,
+};
 
 /// A drawing canvas that manages the pixel buffer and dirty area tracking.
 ///
@@ -39,57 +66,1314 @@ pub struct Canvas<const N: usize, const W: u32, const H: u32, const O: u8> {
     buffer: [u8; N],
     dirty_area_min: (u32, u32),
     dirty_area_max: (u32, u32),
+    dirty_page_columns: [(u32, u32); PAGE_COUNT],
     display_properties: DisplayProperties<W, H, O>,
+    #[cfg(feature = "diff")]
+    previous_buffer: [u8; N],
+    #[cfg(feature = "debug-flush")]
+    debug_flush: bool,
 }
 
+/// The number of pages the controller can address.
+const PAGE_COUNT: usize = 8;
+
+/// The number of columns of the SH1106 RAM, the longest run a page transfer can send.
+const RAM_COLUMNS: usize = 132;
+
+/// Top left corner of the dirty area without any change, above any logical coordinate in
+/// every rotation so the first change always becomes the new corner.
+const CLEAN_DIRTY_AREA_MIN: (u32, u32) = (u32::MAX, u32::MAX);
+
+/// Ordered dithering matrix used by `DitherMode::Bayer4x4`.
+const BAYER_4X4: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// Column range of a page without any change.
+const CLEAN_PAGE_COLUMNS: (u32, u32) = (u32::MAX, 0);
+
+/// The largest number of unchanged bytes merged into a run of changed bytes, since sending
+/// them is cheaper than addressing a new run.
+#[cfg(feature = "diff")]
+const DIFF_MERGE_GAP: usize = 3;
+
 impl<const N: usize, const W: u32, const H: u32, const O: u8> Canvas<N, W, H, O> {
+    /// Creates an empty canvas.
+    ///
+    /// The size is checked at compile time, so a canvas can't silently drop pixels: `N` must be
+    /// `W * H / 8`, `H` must be a multiple of 8 up to the 8 pages of the controller and the
+    /// panel must fit in the 132 columns of the controller RAM, including the column offset `O`.
     pub(crate) fn new(display_properties: DisplayProperties<W, H, O>) -> Self {
+        const {
+            assert!(
+                N == W as usize * H as usize / 8,
+                "buffer size must be width * height / 8"
+            );
+            assert!(
+                H > 0 && H <= PAGE_COUNT as u32 * 8 && H.is_multiple_of(8),
+                "height must be a multiple of 8 up to 64"
+            );
+            assert!(
+                W > 0 && W + O as u32 <= RAM_COLUMNS as u32,
+                "width and column offset must fit in 132 columns"
+            );
+        };
+
         Canvas {
             buffer: [0; N],
             dirty_area_max: (0, 0),
-            dirty_area_min: display_properties.get_display_size(),
+            dirty_area_min: CLEAN_DIRTY_AREA_MIN,
+            dirty_page_columns: [CLEAN_PAGE_COLUMNS; PAGE_COUNT],
             display_properties,
+            #[cfg(feature = "diff")]
+            previous_buffer: [0; N],
+            #[cfg(feature = "debug-flush")]
+            debug_flush: false,
+        }
+    }
+
+    /// Creates an empty canvas with the given rotation, independent of any display.
+    ///
+    /// This is useful to render frames in RAM or to test drawing code on the host. The size
+    /// is set by the const generics, like `Canvas::<1024, 128, 64, 2>::with_rotation(..)`,
+    /// and checked at compile time like the size of `Sh1106`. A panel taller than the 8
+    /// pages of the controller fails to compile:
+    ///
+    /// ```rust,compile_fail
+    /// use mini_oled::screen::{canvas::Canvas, properties::DisplayRotation};
+    ///
+    /// let canvas: Canvas<2048, 128, 128, 2> = Canvas::with_rotation(DisplayRotation::Rotate0);
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `display_rotation` - The rotation applied to the drawing coordinates.
+    pub fn with_rotation(display_rotation: DisplayRotation) -> Self {
+        Self::new(DisplayProperties::new(display_rotation))
+    }
+
+    pub(crate) fn get_column_offset(&self) -> u8 {
+        self.display_properties.get_column_offset()
+    }
+
+    pub(crate) const fn get_display_size(&self) -> (u32, u32) {
+        self.display_properties.get_display_size()
+    }
+
+    pub(crate) fn get_rotation(&self) -> &DisplayRotation {
+        self.display_properties.get_rotation()
+    }
+
+    pub(crate) fn set_rotation(&mut self, display_rotation: DisplayRotation) {
+        self.display_properties.set_rotation(display_rotation);
+    }
+
+    pub(crate) fn set_logical_rotation(&mut self, display_rotation: DisplayRotation) {
+        self.display_properties
+            .set_logical_rotation(display_rotation);
+    }
+
+    pub(crate) fn set_panel_rotation(&mut self, panel_rotation: DisplayRotation) {
+        self.display_properties.set_panel_rotation(panel_rotation);
+    }
+
+    pub(crate) fn set_panel_mirror(&mut self, panel_mirror: (bool, bool)) {
+        self.display_properties.set_panel_mirror(panel_mirror);
+    }
+
+    pub(crate) fn get_panel_mirror(&self) -> (bool, bool) {
+        self.display_properties.get_panel_mirror()
+    }
+
+    pub(crate) fn get_controller(&self) -> Controller {
+        self.display_properties.get_controller()
+    }
+
+    pub(crate) fn get_panel_commands(&self) -> [Command; 2] {
+        self.display_properties.panel_commands()
+    }
+
+    pub(crate) fn get_init_panel_commands(&self) -> [Command; 2] {
+        self.display_properties.init_panel_commands()
+    }
+
+    /// Sets where the 90 and 270 degree rotations are applied. Default is
+    /// `RotationMode::PerPixel`.
+    ///
+    /// With `RotationMode::FlushTime`, the buffer holds the logical layout in these rotations,
+    /// so drawing doesn't swap the coordinates and horizontal lines are written a byte at a
+    /// time. Every flush transposes the pages it sends instead, see the module documentation
+    /// for the trade-off. `Rotate0` and `Rotate180` are not affected.
+    ///
+    /// The buffer isn't converted, so redraw the content after changing the mode. The whole
+    /// canvas is marked dirty. The width must be a multiple of 8, so the logical layout fills
+    /// whole pages, which is checked at compile time.
+    ///
+    /// # Arguments
+    ///
+    /// * `rotation_mode` - Where the rotation is applied.
+    pub fn set_rotation_mode(&mut self, rotation_mode: RotationMode) {
+        const {
+            assert!(
+                W.is_multiple_of(8),
+                "width must be a multiple of 8 to transpose at flush time"
+            )
+        };
+
+        self.display_properties.set_rotation_mode(rotation_mode);
+        self.force_full_dirty_area();
+    }
+
+    /// Returns where the 90 and 270 degree rotations are applied.
+    pub fn get_rotation_mode(&self) -> RotationMode {
+        self.display_properties.get_rotation_mode()
+    }
+
+    /// Returns a reference to the pixel buffer.
+    ///
+    /// The buffer uses the controller layout, except in the 90 and 270 degree rotations with
+    /// `RotationMode::FlushTime`, where it holds `W / 8` pages of `H` logical columns.
+    pub fn get_buffer(&self) -> &[u8; N] {
+        &self.buffer
+    }
+
+    /// Returns a mutable reference to the pixel buffer, in the layout described by
+    /// `get_buffer`.
+    pub fn get_mut_buffer(&mut self) -> &mut [u8; N] {
+        &mut self.buffer
+    }
+
+    /// Returns the `W` bytes of `page`, one per column from the left, with bit 0 at the top
+    /// row of the page.
+    ///
+    /// This is the layout sent to the controller, with the rotation already applied, so the
+    /// bytes can go to a custom transport or a screenshot as they are. Pages below the bottom
+    /// of the panel return an empty slice, and so does every page while the buffer holds the
+    /// logical layout of `RotationMode::FlushTime`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mini_oled::{command::Page, screen::canvas::Canvas};
+    ///
+    /// let mut canvas: Canvas<1024, 128, 64, 2> = Canvas::default();
+    /// canvas.set_pixel(3, 9, true);
+    /// assert_eq!(canvas.page_bytes(Page::Page1)[3], 0b10);
+    /// ```
+    pub fn page_bytes(&self, page: Page) -> &[u8] {
+        if self.is_transposed() {
+            return &[];
+        }
+
+        let offset = page.byte_offset(W);
+        self.buffer.get(offset..offset + W as usize).unwrap_or(&[])
+    }
+
+    /// Returns every page of the panel from the top, with its bytes as `page_bytes` does.
+    ///
+    /// Nothing is returned while the buffer holds the logical layout of
+    /// `RotationMode::FlushTime`.
+    pub fn pages(&self) -> impl Iterator<Item = (Page, &[u8])> {
+        let page_count = match self.is_transposed() {
+            true => 0,
+            false => PAGE_COUNT,
+        };
+        Page::all()
+            .zip(self.buffer.chunks_exact(W as usize))
+            .take(page_count)
+    }
+
+    /// Returns a guard giving mutable access to the pixel buffer, which marks the whole
+    /// buffer dirty when dropped.
+    ///
+    /// Unlike `get_mut_buffer()`, the next `flush()` can't miss an edit. This is coarse, since
+    /// the whole display is sent even if a single byte changed; use `mark_dirty()` to send
+    /// less.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// canvas.buffer_guard()[0] = 0xFF;
+    /// screen.flush()?;
+    /// ```
+    pub fn buffer_guard(&mut self) -> BufferGuard<'_, N, W, H, O> {
+        BufferGuard { canvas: self }
+    }
+
+    /// Sends the outline of every flushed page run inverted, to see on the panel which bytes
+    /// each `flush` updated. Each page sends its own column range, so the outlines are 8 pixels
+    /// high and stacked. The buffer keeps the drawn pixels, so the outline disappears when
+    /// that area is flushed again with this mode disabled.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// screen.get_mut_canvas().set_debug_flush(true);
+    /// screen.get_mut_canvas().set_pixel(10, 10, true);
+    /// screen.flush()?; // Shows column 10 of page 1 inverted, hiding the pixel
+    /// ```
+    #[cfg(feature = "debug-flush")]
+    pub fn set_debug_flush(&mut self, enabled: bool) {
+        self.debug_flush = enabled;
+    }
+
+    pub(crate) fn get_dirty_area(&self) -> ((u32, u32), (u32, u32)) {
+        (self.dirty_area_min, self.dirty_area_max)
+    }
+
+    /// Returns the page address commands and the dirty pixel bytes of every page that
+    /// has changed. Each page only covers its own changed columns, split into chunks of at
+    /// most `max_bytes`.
+    pub(crate) fn dirty_pages(
+        &self,
+        max_bytes: usize,
+    ) -> impl Iterator<Item = (CommandBuffer<2>, PageData<'_>)> {
+        self.page_transfers(self.dirty_page_columns, max_bytes)
+    }
+
+    /// Returns the page address commands and the pixel bytes of every page of the display,
+    /// each covering the full width, split into chunks of at most `max_bytes`.
+    ///
+    /// Unlike `dirty_pages`, this ignores the dirty area, so there are no column ranges to
+    /// derive.
+    pub(crate) fn full_pages(
+        &self,
+        max_bytes: usize,
+    ) -> impl Iterator<Item = (CommandBuffer<2>, PageData<'_>)> {
+        self.page_transfers([(0, W - 1); PAGE_COUNT], max_bytes)
+    }
+
+    /// Returns the page address commands and the pixel bytes of every page covering the
+    /// logical rectangle at `(x, y)` with size `w` x `h`, regardless of the dirty area.
+    ///
+    /// The rectangle is clipped to the display and pages are split into chunks of at most
+    /// `max_bytes`.
+    pub(crate) fn region_pages(
+        &self,
+        x: u32,
+        y: u32,
+        w: u32,
+        h: u32,
+        max_bytes: usize,
+    ) -> impl Iterator<Item = (CommandBuffer<2>, PageData<'_>)> {
+        let (width, height) = self.get_rotated_size();
+        let mut page_columns = [CLEAN_PAGE_COLUMNS; PAGE_COUNT];
+        if w > 0 && h > 0 && x < width && y < height {
+            let min = (x, y);
+            let max = (
+                x.saturating_add(w - 1).min(width - 1),
+                y.saturating_add(h - 1).min(height - 1),
+            );
+            let ((column_min, page_min), (column_max, page_max)) = self.get_page_area(min, max);
+            expand_page_columns(
+                &mut page_columns,
+                column_min,
+                column_max,
+                page_min,
+                page_max,
+            );
+        }
+
+        self.page_transfers(page_columns, max_bytes)
+    }
+
+    fn page_transfers(
+        &self,
+        page_columns: [(u32, u32); PAGE_COUNT],
+        max_bytes: usize,
+    ) -> impl Iterator<Item = (CommandBuffer<2>, PageData<'_>)> {
+        Page::all()
+            .zip(page_columns)
+            // Skip pages below the panel instead of ending the iteration.
+            .take((H >> 3) as usize)
+            .flat_map(move |(page, (column_min, column_max))| {
+                // Clamp to the last column of the page, so a page never runs into the next one.
+                self.page_chunks(page, column_min, column_max.min(W - 1), max_bytes)
+            })
+    }
+
+    /// Splits the columns from `column_min` to `column_max` of `page` into chunks of at most
+    /// `max_bytes`, each with the commands addressing its first column.
+    fn page_chunks(
+        &self,
+        page: Page,
+        column_min: u32,
+        column_max: u32,
+        max_bytes: usize,
+    ) -> impl Iterator<Item = (CommandBuffer<2>, PageData<'_>)> {
+        let column_offset = self.get_column_offset() as u32;
+        let max_bytes = max_bytes.clamp(1, RAM_COLUMNS);
+
+        (column_min..=column_max)
+            .step_by(max_bytes)
+            .map(move |column| {
+                let chunk_max = (column + max_bytes as u32 - 1).min(column_max);
+                (
+                    page_commands(page, column + column_offset),
+                    self.page_data(page, column, chunk_max),
+                )
+            })
+    }
+
+    /// Returns the bytes of `page` from `column_min` to `column_max` as sent to the
+    /// controller, transposed from the logical layout of `RotationMode::FlushTime` if needed.
+    fn page_data(&self, page: Page, column_min: u32, column_max: u32) -> PageData<'_> {
+        if self.is_transposed() {
+            let mut bytes = [0; RAM_COLUMNS];
+            for (byte, column) in bytes.iter_mut().zip(column_min..=column_max) {
+                *byte = self.physical_byte(&self.buffer, page as u32, column);
+            }
+            return PageData::Transposed(bytes, (column_max - column_min + 1) as usize);
+        }
+
+        let page_start_idx = page.byte_offset(W);
+        PageData::Buffer(
+            &self.buffer
+                [page_start_idx + column_min as usize..=page_start_idx + column_max as usize],
+        )
+    }
+
+    /// Returns the page address commands and the pixel bytes of every run of bytes that
+    /// differs from the previous frame. Runs separated by a few unchanged bytes are merged,
+    /// then split into chunks of at most `max_bytes`.
+    #[cfg(feature = "diff")]
+    pub(crate) fn changed_runs(
+        &self,
+        max_bytes: usize,
+    ) -> impl Iterator<Item = (CommandBuffer<2>, PageData<'_>)> {
+        let width = W as usize;
+
+        Page::all().take((H >> 3) as usize).flat_map(move |page| {
+            let changed = move |column: usize| {
+                self.physical_byte(&self.buffer, page as u32, column as u32)
+                    != self.physical_byte(&self.previous_buffer, page as u32, column as u32)
+            };
+
+            let mut column = 0;
+            core::iter::from_fn(move || {
+                let run_start = (column..width).find(|&c| changed(c))?;
+                let mut run_end = run_start;
+                for c in run_start + 1..width {
+                    if changed(c) {
+                        run_end = c;
+                    } else if c - run_end > DIFF_MERGE_GAP {
+                        break;
+                    }
+                }
+                column = run_end + 1;
+
+                Some((run_start as u32, run_end as u32))
+            })
+            .flat_map(move |(run_start, run_end)| {
+                self.page_chunks(page, run_start, run_end, max_bytes)
+            })
+        })
+    }
+
+    /// Records the buffer as the frame shown on the display, for `changed_runs`.
+    #[cfg(feature = "diff")]
+    pub(crate) fn commit_frame(&mut self) {
+        self.previous_buffer = self.buffer;
+    }
+
+    /// Returns `true` if the buffer changed since the last flush.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty_area_min.0 <= self.dirty_area_max.0
+            && self.dirty_area_min.1 <= self.dirty_area_max.1
+    }
+
+    /// Sends the modified parts of the buffer through `communication_interface` and clears
+    /// the dirty area.
+    ///
+    /// This is what `Sh1106::flush` does, for setups where the canvas and the interface are
+    /// owned separately, like rendering in one task and transmitting in another. Nothing is
+    /// sent if the buffer isn't dirty.
+    ///
+    /// With `set_debug_flush(true)`, the outline of every page run is sent inverted, so the
+    /// panel shows which area each flush updated. The buffer itself is not changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `communication_interface` - The interface connected to the display.
+    ///
+    /// # Returns
+    ///
+    /// The number of display data bytes sent, `0` if nothing changed.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// canvas.set_pixel(10, 20, true);
+    /// canvas.flush_to(&mut i2c_interface)?;
+    /// ```
+    pub fn flush_to<CI: CommunicationInterface>(
+        &mut self,
+        communication_interface: &mut CI,
+    ) -> Result<usize, MiniOledError> {
+        self.flush_detailed_to(communication_interface)
+            .map(|stats| stats.data_bytes)
+    }
+
+    /// Sends the modified parts of the buffer like `flush_to`, and returns the pages and
+    /// bytes sent.
+    pub fn flush_detailed_to<CI: CommunicationInterface>(
+        &mut self,
+        communication_interface: &mut CI,
+    ) -> Result<FlushStats, MiniOledError> {
+        if !self.is_dirty() {
+            return Ok(FlushStats::default());
+        }
+
+        #[cfg(feature = "debug-flush")]
+        if self.debug_flush {
+            self.toggle_dirty_border();
+        }
+        let stats = write_pages(
+            self.dirty_pages(CI::MAX_DATA_BYTES),
+            communication_interface,
+        );
+        #[cfg(feature = "debug-flush")]
+        if self.debug_flush {
+            self.toggle_dirty_border();
+        }
+
+        let stats = stats?;
+        self.reset_dirty_area();
+        Ok(stats)
+    }
+
+    /// Asynchronously sends the modified parts of the buffer like `flush_to`.
+    #[cfg(feature = "async")]
+    pub async fn flush_to_async<CI: AsyncCommunicationInterface>(
+        &mut self,
+        communication_interface: &mut CI,
+    ) -> Result<usize, MiniOledError> {
+        self.flush_detailed_to_async(communication_interface)
+            .await
+            .map(|stats| stats.data_bytes)
+    }
+
+    /// Asynchronously sends the modified parts of the buffer like `flush_detailed_to`.
+    #[cfg(feature = "async")]
+    pub async fn flush_detailed_to_async<CI: AsyncCommunicationInterface>(
+        &mut self,
+        communication_interface: &mut CI,
+    ) -> Result<FlushStats, MiniOledError> {
+        if !self.is_dirty() {
+            return Ok(FlushStats::default());
+        }
+
+        #[cfg(feature = "debug-flush")]
+        if self.debug_flush {
+            self.toggle_dirty_border();
+        }
+        let stats = write_pages_async(
+            self.dirty_pages(CI::MAX_DATA_BYTES),
+            communication_interface,
+        )
+        .await;
+        #[cfg(feature = "debug-flush")]
+        if self.debug_flush {
+            self.toggle_dirty_border();
+        }
+
+        let stats = stats?;
+        self.reset_dirty_area();
+        Ok(stats)
+    }
+
+    /// Inverts the 1 pixel outline of every page run the flush sends, so the panel shows what
+    /// each flush updated until it is redrawn. A second call restores the buffer.
+    ///
+    /// The bytes are changed in place, so the dirty area and the runs sent stay the same.
+    #[cfg(feature = "debug-flush")]
+    fn toggle_dirty_border(&mut self) {
+        for (page, (column_min, column_max)) in (0..H >> 3).zip(self.dirty_page_columns) {
+            let column_max = column_max.min(W - 1);
+            for column in column_min..=column_max {
+                // The first and last columns are inverted whole, the others on the top and
+                // bottom rows of the page, so every outline pixel is inverted exactly once.
+                let bit_mask = match column == column_min || column == column_max {
+                    true => 0xFF,
+                    false => 0x81,
+                };
+                let byte = self.physical_byte(&self.buffer, page, column);
+                self.write_physical_byte(page, column, byte ^ bit_mask);
+            }
+        }
+    }
+
+    /// Marks a rectangle of the buffer as changed, so the next `flush()` sends it.
+    ///
+    /// Use this after editing the buffer returned by `get_mut_buffer()` directly.
+    /// The coordinates are `(column, row)` in the buffer layout described by `get_buffer`,
+    /// which is the controller layout regardless of the rotation unless the rotation is
+    /// applied at flush time. The rectangle is clamped to the buffer and merged with the
+    /// existing dirty area.
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - The inclusive top left corner `(column, row)` of the changed rectangle.
+    /// * `max` - The inclusive bottom right corner `(column, row)` of the changed rectangle.
+    pub fn mark_dirty(&mut self, min: (u32, u32), max: (u32, u32)) {
+        let (width, height) = self.get_storage_size();
+        let max = (max.0.min(width - 1), max.1.min(height - 1));
+        if min.0 > max.0 || min.1 > max.1 {
+            return;
+        }
+
+        match self.is_transposed() {
+            true => self.expand_dirty_area(min, max),
+            false => self.expand_physical_dirty_area(min, max),
+        }
+    }
+
+    /// Grows the dirty area so that it covers the inclusive `(column, row)` rectangle of the
+    /// panel from `min` to `max`.
+    fn expand_physical_dirty_area(&mut self, min: (u32, u32), max: (u32, u32)) {
+        let (logical_min, logical_max) = self.get_logical_area(min, max);
+        self.expand_dirty_bounding_box(logical_min, logical_max);
+        self.expand_dirty_pages(min.0, max.0, min.1 >> 3, max.1 >> 3);
+    }
+
+    /// Writes `value` into every byte of `page` from column `x_start` to `x_end`, and marks
+    /// them dirty.
+    ///
+    /// This is the fastest way to fill a page aligned band, for example an 8 pixel high status
+    /// bar. `value` is the raw column byte, bit 0 is the top row of the page. This uses the
+    /// controller layout regardless of the rotation, and writes the pixels one by one when the
+    /// buffer holds the logical layout of `RotationMode::FlushTime`. Columns past the display
+    /// are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `page` - The page to write.
+    /// * `x_start` - The first column, inclusive.
+    /// * `x_end` - The last column, inclusive.
+    /// * `value` - The byte written to every column.
+    pub fn fill_page(&mut self, page: Page, x_start: u32, x_end: u32, value: u8) {
+        let x_end = x_end.min(W - 1);
+        let page_start_idx = page.byte_offset(W);
+        if x_start > x_end || page_start_idx >= N {
+            return;
+        }
+
+        match self.is_transposed() {
+            true => {
+                for column in x_start..=x_end {
+                    self.write_physical_byte(page as u32, column, value);
+                }
+            }
+            false => self.buffer
+                [page_start_idx + x_start as usize..=page_start_idx + x_end as usize]
+                .fill(value),
+        }
+        let row = (page as u32) << 3;
+        self.expand_physical_dirty_area((x_start, row), (x_end, row + 7));
+    }
+
+    /// Fills the whole buffer with `pattern`, in controller layout regardless of the rotation.
+    pub(crate) fn draw_test_pattern(&mut self, pattern: TestPattern) {
+        let last_page = (H - 1) >> 3;
+        for page in 0..=last_page {
+            for column in 0..W {
+                let byte = match pattern {
+                    TestPattern::Checkerboard => (-((((column >> 3) + page) & 1 == 0) as i8)) as u8,
+                    TestPattern::VerticalStripes => (-((column & 1 == 0) as i8)) as u8,
+                    TestPattern::Border if column == 0 || column == W - 1 => 0xFF,
+                    TestPattern::Border => {
+                        let top = if page == 0 { 0x01 } else { 0x00 };
+                        let bottom = if page == last_page {
+                            1 << ((H - 1) & 7)
+                        } else {
+                            0x00
+                        };
+                        top | bottom
+                    }
+                };
+                self.write_physical_byte(page, column, byte);
+            }
+        }
+        self.force_full_dirty_area();
+    }
+
+    pub(crate) fn force_full_dirty_area(&mut self) {
+        let (width, height) = self.get_rotated_size();
+        self.dirty_area_min = (0, 0);
+        self.dirty_area_max = (width - 1, height - 1);
+        self.dirty_page_columns = [CLEAN_PAGE_COLUMNS; PAGE_COUNT];
+        self.expand_dirty_pages(0, W - 1, 0, (H - 1) >> 3);
+    }
+
+    pub(crate) fn reset_dirty_area(&mut self) {
+        self.dirty_area_min = CLEAN_DIRTY_AREA_MIN;
+        self.dirty_area_max = (0, 0);
+        self.dirty_page_columns = [CLEAN_PAGE_COLUMNS; PAGE_COUNT];
+    }
+
+    /// Grows the dirty area so that it covers the inclusive logical rectangle from `min` to `max`.
+    fn expand_dirty_area(&mut self, min: (u32, u32), max: (u32, u32)) {
+        self.expand_dirty_bounding_box(min, max);
+
+        let ((column_min, page_min), (column_max, page_max)) = self.get_page_area(min, max);
+        self.expand_dirty_pages(column_min, column_max, page_min, page_max);
+    }
+
+    /// Returns the inclusive `(column, page)` corners covering the inclusive logical
+    /// rectangle from `min` to `max`.
+    fn get_page_area(&self, min: (u32, u32), max: (u32, u32)) -> ((u32, u32), (u32, u32)) {
+        let ((column_min, row_min), (column_max, row_max)) = self.get_physical_area(min, max);
+        ((column_min, row_min >> 3), (column_max, row_max >> 3))
+    }
+
+    /// Returns the inclusive `(column, row)` corners in the buffer covering the inclusive
+    /// logical rectangle from `min` to `max`.
+    fn get_physical_area(&self, min: (u32, u32), max: (u32, u32)) -> ((u32, u32), (u32, u32)) {
+        // In rotated modes the logical X axis runs along the page bits.
+        let ((column_min, row_min), (column_max, row_max)) =
+            match self.display_properties.get_rotation() {
+                DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => (min, max),
+                DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => {
+                    ((min.1, min.0), (max.1, max.0))
+                }
+            };
+
+        // After `set_logical_rotation` the panel may still be mirrored for another rotation.
+        let (mirror_columns, mirror_rows) = self.display_properties.get_mirroring();
+        let (column_min, column_max) = match mirror_columns {
+            true => (W - 1 - column_max, W - 1 - column_min),
+            false => (column_min, column_max),
+        };
+        let (row_min, row_max) = match mirror_rows {
+            true => (H - 1 - row_max, H - 1 - row_min),
+            false => (row_min, row_max),
+        };
+
+        ((column_min, row_min), (column_max, row_max))
+    }
+
+    /// Returns the inclusive logical corners covering the inclusive `(column, row)` rectangle
+    /// in the buffer from `min` to `max`, the inverse of `get_physical_area`.
+    fn get_logical_area(&self, min: (u32, u32), max: (u32, u32)) -> ((u32, u32), (u32, u32)) {
+        let (mirror_columns, mirror_rows) = self.display_properties.get_mirroring();
+        let (column_min, column_max) = match mirror_columns {
+            true => (W - 1 - max.0, W - 1 - min.0),
+            false => (min.0, max.0),
+        };
+        let (row_min, row_max) = match mirror_rows {
+            true => (H - 1 - max.1, H - 1 - min.1),
+            false => (min.1, max.1),
+        };
+
+        match self.display_properties.get_rotation() {
+            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => {
+                ((column_min, row_min), (column_max, row_max))
+            }
+            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => {
+                ((row_min, column_min), (row_max, column_max))
+            }
+        }
+    }
+
+    /// Returns `true` if the buffer holds the logical layout, which `RotationMode::FlushTime`
+    /// uses in the 90 and 270 degree rotations.
+    fn is_transposed(&self) -> bool {
+        self.display_properties.get_rotation_mode() == RotationMode::FlushTime
+            && matches!(
+                self.display_properties.get_rotation(),
+                DisplayRotation::Rotate90 | DisplayRotation::Rotate270
+            )
+    }
+
+    /// Returns the `(width, height)` of the buffer layout, which is the logical size when
+    /// the buffer is transposed and the panel size otherwise.
+    fn get_storage_size(&self) -> (u32, u32) {
+        match self.is_transposed() {
+            true => (H, W),
+            false => (W, H),
+        }
+    }
+
+    /// Returns the inclusive `(column, row)` corners in the buffer layout covering the
+    /// inclusive logical rectangle from `min` to `max`.
+    fn get_storage_area(&self, min: (u32, u32), max: (u32, u32)) -> ((u32, u32), (u32, u32)) {
+        match self.is_transposed() {
+            true => (min, max),
+            false => self.get_physical_area(min, max),
+        }
+    }
+
+    /// Returns the page, the column and the bit mask of the byte in the buffer layout that
+    /// holds the logical pixel `(x, y)`.
+    #[inline]
+    fn get_storage_position(&self, x: u32, y: u32) -> (u32, u32, u8) {
+        match self.is_transposed() {
+            true => (y >> 3, x, 1 << (y & 7)),
+            false => self.get_pixel_position(x, y),
+        }
+    }
+
+    /// Returns the index of the byte at `column` of `page` in the buffer layout.
+    #[inline]
+    fn get_storage_index(&self, page: u32, column: u32) -> usize {
+        (fast_mul(page, self.get_storage_size().0) + column) as usize
+    }
+
+    /// Returns the buffer index and the bit mask of bit 0 of the byte at `column` of `page`
+    /// sent to the controller, in the logical layout of a transposed buffer.
+    ///
+    /// The 8 bits of the byte are the same bit of consecutive buffer bytes, which run towards
+    /// lower indices if the returned flag is set.
+    fn get_transposed_bits(&self, page: u32, column: u32) -> (usize, bool, u8) {
+        let row = page << 3;
+        let ((x, y), _) = self.get_logical_area((column, row), (column, row));
+        let (_, mirror_rows) = self.display_properties.get_mirroring();
+        let (page, column, bit_mask) = self.get_storage_position(x, y);
+        (self.get_storage_index(page, column), mirror_rows, bit_mask)
+    }
+
+    /// Returns the byte at `column` of `page` as sent to the controller, read from `buffer`,
+    /// which is either the canvas buffer or the previous frame.
+    fn physical_byte(&self, buffer: &[u8; N], page: u32, column: u32) -> u8 {
+        if !self.is_transposed() {
+            return buffer[(fast_mul(page, W) + column) as usize];
+        }
+
+        let (idx, reversed, bit_mask) = self.get_transposed_bits(page, column);
+        (0..8).fold(0, |byte, bit| {
+            let idx = if reversed { idx - bit } else { idx + bit };
+            byte | (((buffer[idx] & bit_mask != 0) as u8) << bit)
+        })
+    }
+
+    /// Writes `value` as the byte at `column` of `page` sent to the controller, without dirty
+    /// area tracking.
+    fn write_physical_byte(&mut self, page: u32, column: u32, value: u8) {
+        if !self.is_transposed() {
+            self.buffer[(fast_mul(page, W) + column) as usize] = value;
+            return;
+        }
+
+        let (idx, reversed, bit_mask) = self.get_transposed_bits(page, column);
+        for bit in 0..8 {
+            let idx = if reversed { idx - bit } else { idx + bit };
+            let pixel_status_mask = (-(((value >> bit) & 1) as i8)) as u8;
+            self.buffer[idx] = (self.buffer[idx] & !bit_mask) | (pixel_status_mask & bit_mask);
+        }
+    }
+
+    fn expand_dirty_bounding_box(&mut self, min: (u32, u32), max: (u32, u32)) {
+        self.dirty_area_min.0 = self.dirty_area_min.0.min(min.0);
+        self.dirty_area_min.1 = self.dirty_area_min.1.min(min.1);
+        self.dirty_area_max.0 = self.dirty_area_max.0.max(max.0);
+        self.dirty_area_max.1 = self.dirty_area_max.1.max(max.1);
+    }
+
+    /// Grows the changed column range of every page from `page_min` to `page_max`.
+    fn expand_dirty_pages(
+        &mut self,
+        column_min: u32,
+        column_max: u32,
+        page_min: u32,
+        page_max: u32,
+    ) {
+        expand_page_columns(
+            &mut self.dirty_page_columns,
+            column_min,
+            column_max,
+            page_min,
+            page_max,
+        );
+    }
+
+    /// Returns the logical size of the canvas for the current rotation.
+    pub(crate) fn get_rotated_size(&self) -> (u32, u32) {
+        match self.display_properties.get_rotation() {
+            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => (W, H),
+            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => (H, W),
+        }
+    }
+
+    /// Sets every pixel in the inclusive logical rectangle from `min` to `max`, which must
+    /// already be clipped to the display, and marks it dirty.
+    #[cfg(feature = "embedded-graphics-core")]
+    fn fill_area(&mut self, min: (u32, u32), max: (u32, u32), pixel_status: bool) {
+        let pixel_status_mask = (-(pixel_status as i8)) as u8;
+        self.modify_area(min, max, |byte, bit_mask| {
+            (byte & !bit_mask) | (pixel_status_mask & bit_mask)
+        });
+    }
+
+    /// Applies `modify` to every byte covering the inclusive logical rectangle from `min` to
+    /// `max`, which must already be clipped to the display, and marks it dirty.
+    ///
+    /// `modify` receives the current byte and the mask of the bits inside the rectangle,
+    /// and returns the new byte.
+    fn modify_area(&mut self, min: (u32, u32), max: (u32, u32), modify: impl Fn(u8, u8) -> u8) {
+        self.expand_dirty_area(min, max);
+
+        let ((column_min, bit_min), (column_max, bit_max)) = self.get_storage_area(min, max);
+
+        for page in (bit_min >> 3)..=(bit_max >> 3) {
+            let first_bit = if page == bit_min >> 3 { bit_min & 7 } else { 0 };
+            let last_bit = if page == bit_max >> 3 { bit_max & 7 } else { 7 };
+            let bit_mask = (0xFFu8 >> (7 - last_bit)) & (0xFFu8 << first_bit);
+
+            let page_start_idx = self.get_storage_index(page, 0);
+            let columns = self
+                .buffer
+                .get_mut(
+                    page_start_idx + column_min as usize..=page_start_idx + column_max as usize,
+                )
+                .unwrap_or_default();
+            for byte in columns {
+                *byte = modify(*byte, bit_mask);
+            }
+        }
+    }
+
+    /// Inverts every pixel in a rectangle, for example to highlight a selected menu entry.
+    ///
+    /// Only the rectangle is marked dirty. Pixels outside of the display are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The X coordinate of the top left corner.
+    /// * `y` - The Y coordinate of the top left corner.
+    /// * `w` - The width of the rectangle in pixels.
+    /// * `h` - The height of the rectangle in pixels.
+    pub fn invert_region(&mut self, x: u32, y: u32, w: u32, h: u32) {
+        let (width, height) = self.get_rotated_size();
+        if w == 0 || h == 0 || x >= width || y >= height {
+            return;
+        }
+        let max = (
+            x.saturating_add(w - 1).min(width - 1),
+            y.saturating_add(h - 1).min(height - 1),
+        );
+
+        self.modify_area((x, y), max, |byte, bit_mask| byte ^ bit_mask);
+    }
+
+    /// Scrolls the content up by `rows` pixels, for example to make room for a new line in a
+    /// log view.
+    ///
+    /// The rows scrolled in at the bottom are set to `fill` and the whole display is marked dirty.
+    ///
+    /// # Arguments
+    ///
+    /// * `rows` - The number of rows to scroll by.
+    /// * `fill` - `true` to turn the vacated pixels on, `false` to turn them off.
+    pub fn scroll_up(&mut self, rows: u32, fill: bool) {
+        self.scroll_rows(rows, fill, true);
+    }
+
+    /// Scrolls the content down by `rows` pixels.
+    ///
+    /// The rows scrolled in at the top are set to `fill` and the whole display is marked dirty.
+    ///
+    /// # Arguments
+    ///
+    /// * `rows` - The number of rows to scroll by.
+    /// * `fill` - `true` to turn the vacated pixels on, `false` to turn them off.
+    pub fn scroll_down(&mut self, rows: u32, fill: bool) {
+        self.scroll_rows(rows, fill, false);
+    }
+
+    fn scroll_rows(&mut self, rows: u32, fill: bool, up: bool) {
+        let fill_byte = (-(fill as i8)) as u8;
+        let rows = rows.min(self.get_rotated_size().1);
+        let (storage_width, storage_height) = self.get_storage_size();
+        let page_count = storage_height >> 3;
+        let width = storage_width as usize;
+        // A transposed buffer holds the logical layout, which is never mirrored.
+        let (mirror_columns, mirror_rows) = match self.is_transposed() {
+            true => (false, false),
+            false => self.display_properties.get_mirroring(),
+        };
+
+        match self.display_properties.get_rotation() {
+            // Logical rows are the columns, each page moves as a slice of bytes.
+            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 if !self.is_transposed() => {
+                let up = up != mirror_columns;
+                let rows = rows as usize;
+                for page in self.buffer.chunks_exact_mut(width) {
+                    match up {
+                        true => {
+                            page.copy_within(rows.., 0);
+                            page[width - rows..].fill(fill_byte);
+                        }
+                        false => {
+                            page.copy_within(..width - rows, rows);
+                            page[..rows].fill(fill_byte);
+                        }
+                    }
+                }
+            }
+            // Logical rows are the bits of the pages, whole pages move as bytes and the
+            // remainder is shifted in from the neighbouring page.
+            _ => {
+                let up = up != mirror_rows;
+                let page_shift = rows >> 3;
+                let bit_shift = rows & 7;
+                let old_page = |buffer: &[u8; N], page: i64, column: usize| match page {
+                    page if page < 0 || page >= page_count as i64 => fill_byte,
+                    page => buffer[page as usize * width + column],
+                };
+
+                for step in 0..page_count {
+                    let page = match up {
+                        true => step,
+                        false => page_count - 1 - step,
+                    };
+                    for column in 0..width {
+                        let byte = match up {
+                            true => {
+                                let source = (page + page_shift) as i64;
+                                let low = old_page(&self.buffer, source, column);
+                                let high = old_page(&self.buffer, source + 1, column);
+                                match bit_shift {
+                                    0 => low,
+                                    _ => (low >> bit_shift) | (high << (8 - bit_shift)),
+                                }
+                            }
+                            false => {
+                                let source = page as i64 - page_shift as i64;
+                                let high = old_page(&self.buffer, source, column);
+                                let low = old_page(&self.buffer, source - 1, column);
+                                match bit_shift {
+                                    0 => high,
+                                    _ => (high << bit_shift) | (low >> (8 - bit_shift)),
+                                }
+                            }
+                        };
+                        self.buffer[page as usize * width + column] = byte;
+                    }
+                }
+            }
+        }
+
+        self.force_full_dirty_area();
+    }
+
+    /// Draws a horizontal line.
+    ///
+    /// For `Rotate0` and `Rotate180` the line is written directly into the page that holds row
+    /// `y`, updating the dirty area once. Rotated modes fall back to `set_pixel`, unless the
+    /// rotation is applied at flush time with `RotationMode::FlushTime`.
+    /// Pixels outside of the display are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The X coordinate of the leftmost pixel.
+    /// * `y` - The Y coordinate of the line.
+    /// * `len` - The length of the line in pixels.
+    /// * `pixel_status` - `true` to turn the pixels on, `false` to turn them off.
+    pub fn draw_hline(&mut self, x: u32, y: u32, len: u32, pixel_status: bool) {
+        let (width, height) = self.get_rotated_size();
+        if len == 0 || x >= width || y >= height {
+            return;
+        }
+        let x_end = x.saturating_add(len - 1).min(width - 1);
+
+        match self.display_properties.get_rotation() {
+            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 if !self.is_transposed() => {
+                for x in x..=x_end {
+                    self.set_pixel(x, y, pixel_status);
+                }
+            }
+            _ => {
+                let pixel_status_mask = (-(pixel_status as i8)) as u8;
+                self.modify_area((x, y), (x_end, y), |byte, bit_mask| {
+                    (byte & !bit_mask) | (pixel_status_mask & bit_mask)
+                });
+            }
+        }
+    }
+
+    /// Draws a vertical line.
+    ///
+    /// The line is written a byte at a time, masking the partial bytes at both ends and
+    /// filling whole bytes in between, and the dirty area is updated once.
+    /// Pixels outside of the display are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The X coordinate of the line.
+    /// * `y` - The Y coordinate of the topmost pixel.
+    /// * `len` - The length of the line in pixels.
+    /// * `pixel_status` - `true` to turn the pixels on, `false` to turn them off.
+    pub fn draw_vline(&mut self, x: u32, y: u32, len: u32, pixel_status: bool) {
+        let (width, height) = self.get_rotated_size();
+        if len == 0 || x >= width || y >= height {
+            return;
+        }
+        let y_end = y.saturating_add(len - 1).min(height - 1);
+
+        let pixel_status_mask = (-(pixel_status as i8)) as u8;
+        self.modify_area((x, y), (x, y_end), |byte, bit_mask| {
+            (byte & !bit_mask) | (pixel_status_mask & bit_mask)
+        });
+    }
+
+    /// Copies a packed 1 bit per pixel bitmap into the canvas.
+    ///
+    /// The bitmap is stored row by row, each row starting on a new byte and taking
+    /// `width.div_ceil(8)` bytes. Within a byte the most significant bit is the leftmost pixel.
+    /// The height is derived from the length of `data`. Pixels outside of the display are ignored.
+    ///
+    /// Where logical rows lie in the buffer pages, in `Rotate0`, `Rotate180` and the
+    /// `RotationMode::FlushTime` rotations, the bitmap is written a byte at a time. The dirty
+    /// area is updated once.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The X coordinate of the top left corner.
+    /// * `y` - The Y coordinate of the top left corner.
+    /// * `width` - The width of the bitmap in pixels.
+    /// * `data` - The packed bitmap rows.
+    /// * `on_is_set` - `true` if a set bit turns the pixel on, `false` if it turns it off.
+    pub fn blit(&mut self, x: u32, y: u32, width: u32, data: &[u8], on_is_set: bool) {
+        let stride = width.div_ceil(8) as usize;
+        if stride == 0 {
+            return;
+        }
+        let height = (data.len() / stride) as u32;
+        let (canvas_width, canvas_height) = self.get_rotated_size();
+        if height == 0 || x >= canvas_width || y >= canvas_height {
+            return;
+        }
+        let max = (
+            x.saturating_add(width - 1).min(canvas_width - 1),
+            y.saturating_add(height - 1).min(canvas_height - 1),
+        );
+
+        let pixel_status = |pixel_x: u32, pixel_y: u32| {
+            let (column, row) = (pixel_x - x, pixel_y - y);
+            let byte = data[row as usize * stride + (column >> 3) as usize];
+            (byte & (0x80 >> (column & 7)) != 0) == on_is_set
+        };
+
+        match self.display_properties.get_rotation() {
+            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 if !self.is_transposed() => {
+                // Logical rows are the columns of the buffer, one bit per byte.
+                for pixel_y in y..=max.1 {
+                    for pixel_x in x..=max.0 {
+                        self.write_pixel(pixel_x, pixel_y, pixel_status(pixel_x, pixel_y));
+                    }
+                }
+            }
+            _ => self.write_area((x, y), max, pixel_status),
+        }
+        self.expand_dirty_area((x, y), max);
+    }
+
+    /// Writes the logical rectangle from `min` to `max`, which must already be clipped to the
+    /// display, a byte at a time, without dirty area tracking.
+    ///
+    /// `pixel_status` returns the state of a logical pixel. The rotation must keep the logical
+    /// rows in the buffer pages, like `Rotate0`, `Rotate180` and a transposed buffer.
+    fn write_area(
+        &mut self,
+        min: (u32, u32),
+        max: (u32, u32),
+        pixel_status: impl Fn(u32, u32) -> bool,
+    ) {
+        let ((column_min, row_min), (column_max, row_max)) = self.get_storage_area(min, max);
+        // The rectangle runs towards lower columns or rows when the panel is mirrored.
+        let ((first_column, first_row), _) = self.get_storage_area(min, min);
+        let logical_x = |column: u32| match first_column == column_min {
+            true => min.0 + (column - column_min),
+            false => max.0 - (column - column_min),
+        };
+        let logical_y = |row: u32| match first_row == row_min {
+            true => min.1 + (row - row_min),
+            false => max.1 - (row - row_min),
+        };
+
+        for page in (row_min >> 3)..=(row_max >> 3) {
+            let first_bit = if page == row_min >> 3 { row_min & 7 } else { 0 };
+            let last_bit = if page == row_max >> 3 { row_max & 7 } else { 7 };
+            let bit_mask = (0xFFu8 >> (7 - last_bit)) & (0xFFu8 << first_bit);
+
+            let page_start_idx = self.get_storage_index(page, 0);
+            for column in column_min..=column_max {
+                let x = logical_x(column);
+                let byte = (first_bit..=last_bit).fold(0u8, |byte, bit| {
+                    byte | ((pixel_status(x, logical_y((page << 3) + bit)) as u8) << bit)
+                });
+                let idx = page_start_idx + column as usize;
+                self.buffer[idx] = (self.buffer[idx] & !bit_mask) | (byte & bit_mask);
+            }
         }
     }
 
-    pub(crate) fn get_column_offset(&self) -> u8 {
-        self.display_properties.get_column_offset()
-    }
+    /// Combines a packed 1 bit per pixel bitmap with the content of the canvas.
+    ///
+    /// The bitmap layout is the one of `blit`. `BlitMode::Or` leaves the pixels under clear
+    /// bits untouched, which draws icons with a transparent background, and `BlitMode::Xor`
+    /// inverts the pixels under set bits. Only the pixels that may change are marked dirty.
+    /// Pixels outside of the display are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The X coordinate of the top left corner.
+    /// * `y` - The Y coordinate of the top left corner.
+    /// * `width` - The width of the bitmap in pixels.
+    /// * `data` - The packed bitmap rows.
+    /// * `mode` - How the bitmap is combined with the canvas.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mini_oled::screen::{canvas::Canvas, properties::BlitMode};
+    ///
+    /// let mut canvas: Canvas<1024, 128, 64, 2> = Canvas::default();
+    /// canvas.draw_bitmap(0, 0, 8, &[0b1000_0001], BlitMode::Or);
+    /// ```
+    pub fn draw_bitmap(&mut self, x: u32, y: u32, width: u32, data: &[u8], mode: BlitMode) {
+        let stride = width.div_ceil(8) as usize;
+        if stride == 0 {
+            return;
+        }
+        let (canvas_width, canvas_height) = self.get_rotated_size();
 
-    pub(crate) const fn get_display_size(&self) -> (u32, u32) {
-        self.display_properties.get_display_size()
-    }
+        for (row, row_data) in (0..).zip(data.chunks_exact(stride)) {
+            for column in 0..width {
+                let (x, y) = (x.saturating_add(column), y.saturating_add(row));
+                if x >= canvas_width || y >= canvas_height {
+                    continue;
+                }
 
-    pub(crate) fn get_rotation(&self) -> &DisplayRotation {
-        self.display_properties.get_rotation()
+                let bit = row_data[(column >> 3) as usize] & (0x80 >> (column & 7)) != 0;
+                match (mode, bit) {
+                    (BlitMode::Replace, _) => self.set_pixel(x, y, bit),
+                    (BlitMode::Or, true) => self.set_pixel(x, y, true),
+                    (BlitMode::And, false) => self.set_pixel(x, y, false),
+                    (BlitMode::Xor, true) => {
+                        self.modify_area((x, y), (x, y), |byte, bit_mask| byte ^ bit_mask)
+                    }
+                    _ => {}
+                }
+            }
+        }
     }
 
-    pub(crate) fn set_rotation(&mut self, display_rotation: DisplayRotation) {
-        self.display_properties.set_rotation(display_rotation);
-    }
+    /// Draws an 8 bit per pixel grayscale image, converting it to 1 bit per pixel.
+    ///
+    /// The image is stored row by row with one byte per pixel, `0` is black and `255` is
+    /// white, which becomes a lit pixel. The height is derived from the length of `data`.
+    /// Pixels outside of the display are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The X coordinate of the top left corner.
+    /// * `y` - The Y coordinate of the top left corner.
+    /// * `width` - The width of the image in pixels.
+    /// * `data` - The grayscale rows.
+    /// * `dither_mode` - How gray levels are turned into lit and dark pixels.
+    pub fn blit_gray(&mut self, x: u32, y: u32, width: u32, data: &[u8], dither_mode: DitherMode) {
+        if width == 0 {
+            return;
+        }
 
-    /// Returns a reference to the pixel buffer.
-    pub fn get_buffer(&self) -> &[u8; N] {
-        &self.buffer
+        for (row, row_data) in (0..).zip(data.chunks_exact(width as usize)) {
+            for (column, &value) in (0..).zip(row_data) {
+                let (x, y) = (x.saturating_add(column), y.saturating_add(row));
+                let threshold = match dither_mode {
+                    DitherMode::Threshold(threshold) => threshold,
+                    // Anchored to the canvas, so neighbouring images tile seamlessly.
+                    DitherMode::Bayer4x4 => BAYER_4X4[(y & 3) as usize][(x & 3) as usize] * 16 + 8,
+                };
+                self.set_pixel(x, y, value >= threshold);
+            }
+        }
     }
 
-    /// Returns a mutable reference to the pixel buffer.
-    pub fn get_mut_buffer(&mut self) -> &mut [u8; N] {
-        &mut self.buffer
-    }
+    /// Sets the state of a single pixel, reporting coordinates outside of the display.
+    ///
+    /// Unlike `set_pixel`, which silently ignores such pixels, this returns an error so
+    /// coordinate mistakes can be caught.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The X coordinate of the pixel.
+    /// * `y` - The Y coordinate of the pixel.
+    /// * `pixel_status` - `true` to turn the pixel on, `false` to turn it off.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` on success, or `MiniOledError::PixelOutOfBounds` if the pixel is outside of the display.
+    pub fn try_set_pixel(
+        &mut self,
+        x: u32,
+        y: u32,
+        pixel_status: bool,
+    ) -> Result<(), MiniOledError> {
+        let (width, height) = self.get_rotated_size();
+        if x >= width || y >= height {
+            return Err(MiniOledError::PixelOutOfBounds { x, y });
+        }
 
-    pub(crate) fn get_dirty_area(&self) -> ((u32, u32), (u32, u32)) {
-        (self.dirty_area_min, self.dirty_area_max)
+        self.set_pixel(x, y, pixel_status);
+        Ok(())
     }
 
-    pub(crate) fn force_full_dirty_area(&mut self) {
-        self.dirty_area_min = (0, 0);
-        self.dirty_area_max = (W - 1, H - 1);
+    /// Returns every pixel of the canvas as `(x, y, on)`, row by row in logical coordinates.
+    ///
+    /// This is useful to export the canvas, for example to render it to an image on a host
+    /// or to compare it against a reference in tests.
+    pub fn pixels(&self) -> impl Iterator<Item = (u32, u32, bool)> + '_ {
+        let (width, height) = self.get_rotated_size();
+        (0..height).flat_map(move |y| {
+            (0..width).map(move |x| {
+                let (page, column, bit_mask) = self.get_storage_position(x, y);
+                (
+                    x,
+                    y,
+                    self.buffer[self.get_storage_index(page, column)] & bit_mask != 0,
+                )
+            })
+        })
     }
 
-    pub(crate) fn reset_dirty_area(&mut self) {
-        self.dirty_area_min = self.display_properties.get_display_size();
-        self.dirty_area_max = (0, 0);
+    /// Writes the canvas as a binary PBM (P4) image in logical orientation into `out`.
+    ///
+    /// Lit pixels are written as 1, which image viewers show as black. The output takes the
+    /// header plus `width.div_ceil(8) * height` bytes, about 1KB for a 128x64 panel.
+    ///
+    /// # Arguments
+    ///
+    /// * `out` - The buffer to write the image into.
+    ///
+    /// # Returns
+    ///
+    /// The written image on success, or `MiniOledError::DataBufferSizeError` if `out` is
+    /// too small.
+    pub fn to_pbm<'a>(&self, out: &'a mut [u8]) -> Result<&'a [u8], MiniOledError> {
+        let (width, height) = self.get_rotated_size();
+        let mut header = PbmHeader::default();
+        header.push(b"P4\n");
+        header.push_decimal(width);
+        header.push(b" ");
+        header.push_decimal(height);
+        header.push(b"\n");
+        let header = header.as_bytes();
+
+        let stride = width.div_ceil(8) as usize;
+        let length = header.len() + stride * height as usize;
+        let out = out
+            .get_mut(..length)
+            .ok_or(MiniOledError::DataBufferSizeError)?;
+        out[..header.len()].copy_from_slice(header);
+
+        let rows = &mut out[header.len()..];
+        rows.fill(0);
+        for (x, y, on) in self.pixels() {
+            if on {
+                rows[y as usize * stride + (x >> 3) as usize] |= 0x80 >> (x & 7);
+            }
+        }
+
+        Ok(out)
     }
 
     #[inline]
@@ -131,18 +1415,41 @@ impl<const N: usize, const W: u32, const H: u32, const O: u8> Canvas<N, W, H, O>
             self.dirty_area_max.1 = y;
         }
 
-        let (idx, bit_mask) = match *display_rotation {
-            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => {
-                let idx = fast_mul!((y >> 3), W) + x; // y >> 3 is equal to y / 8
-                let bit = 1 << (y & 7); // y & 7 is equal to y % 8
-                (idx as usize, bit)
-            }
-            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => {
-                let idx = fast_mul!((x >> 3), W) + y; // y >> 3 is equal to y / 8
-                let bit = 1 << (x & 7); // y & 7 is equal to y % 8
-                (idx as usize, bit)
-            }
-        };
+        let (page, column, bit_mask) = self.get_pixel_position(x, y);
+
+        if let Some((dirty_column_min, dirty_column_max)) =
+            self.dirty_page_columns.get_mut(page as usize)
+        {
+            *dirty_column_min = (*dirty_column_min).min(column);
+            *dirty_column_max = (*dirty_column_max).max(column);
+        }
+
+        match self.is_transposed() {
+            true => self.write_pixel(x, y, pixel_status),
+            false => self.write_bit(page, column, bit_mask, pixel_status),
+        }
+    }
+
+    /// Returns the page, the column and the bit mask of the byte sent to the controller that
+    /// holds the logical pixel `(x, y)`.
+    #[inline]
+    fn get_pixel_position(&self, x: u32, y: u32) -> (u32, u32, u8) {
+        let ((column, row), _) = self.get_physical_area((x, y), (x, y));
+        (row >> 3, column, 1 << (row & 7)) // row >> 3 is equal to row / 8, row & 7 is equal to row % 8
+    }
+
+    /// Writes the logical pixel `(x, y)` into the buffer without bounds checks against the
+    /// display or dirty area tracking.
+    #[inline]
+    fn write_pixel(&mut self, x: u32, y: u32, pixel_status: bool) {
+        let (page, column, bit_mask) = self.get_storage_position(x, y);
+        self.write_bit(page, column, bit_mask, pixel_status);
+    }
+
+    /// Writes the bits of `bit_mask` in the byte at `column` of `page` in the buffer layout.
+    #[inline]
+    fn write_bit(&mut self, page: u32, column: u32, bit_mask: u8, pixel_status: bool) {
+        let idx = self.get_storage_index(page, column);
         /*
            match pixel_status {
                true => self.buffer[idx as usize] |= bit_mask,
@@ -156,11 +1463,200 @@ impl<const N: usize, const W: u32, const H: u32, const O: u8> Canvas<N, W, H, O>
         }
     }
 }
+
+/// Creates an empty canvas without rotation.
+impl<const N: usize, const W: u32, const H: u32, const O: u8> Default for Canvas<N, W, H, O> {
+    fn default() -> Self {
+        Self::with_rotation(DisplayRotation::Rotate0)
+    }
+}
+
+/// Header of a PBM image, `P4`, the width and the height, which fits in 24 bytes.
+#[derive(Default)]
+struct PbmHeader {
+    bytes: [u8; 24],
+    length: usize,
+}
+
+impl PbmHeader {
+    fn push(&mut self, bytes: &[u8]) {
+        self.bytes[self.length..self.length + bytes.len()].copy_from_slice(bytes);
+        self.length += bytes.len();
+    }
+
+    fn push_decimal(&mut self, value: u32) {
+        let mut digits = [0u8; 10];
+        let mut start = digits.len();
+        let mut value = value;
+        loop {
+            start -= 1;
+            digits[start] = b'0' + (value % 10) as u8;
+            value /= 10;
+            if value == 0 {
+                break;
+            }
+        }
+        self.push(&digits[start..]);
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.length]
+    }
+}
+
+/// Mutable access to the pixel buffer of a `Canvas`, returned by `Canvas::buffer_guard`.
+///
+/// It derefs to the buffer and marks the whole canvas dirty when dropped.
+pub struct BufferGuard<'a, const N: usize, const W: u32, const H: u32, const O: u8> {
+    canvas: &'a mut Canvas<N, W, H, O>,
+}
+
+impl<const N: usize, const W: u32, const H: u32, const O: u8> Deref
+    for BufferGuard<'_, N, W, H, O>
+{
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.canvas.buffer
+    }
+}
+
+impl<const N: usize, const W: u32, const H: u32, const O: u8> DerefMut
+    for BufferGuard<'_, N, W, H, O>
+{
+    fn deref_mut(&mut self) -> &mut [u8] {
+        &mut self.canvas.buffer
+    }
+}
+
+impl<const N: usize, const W: u32, const H: u32, const O: u8> Drop for BufferGuard<'_, N, W, H, O> {
+    fn drop(&mut self) {
+        self.canvas.force_full_dirty_area();
+    }
+}
+
+/// Pages and bytes sent by a flush, returned by `Sh1106::flush_detailed`.
+///
+/// Comparing them between flushes shows how much the dirty tracking saves on a board.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let stats = screen.flush_detailed()?;
+/// // stats.data_bytes, stats.command_bytes, stats.pages_sent
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FlushStats {
+    /// Number of pages that received data.
+    pub pages_sent: u8,
+    /// Number of display data bytes sent.
+    pub data_bytes: usize,
+    /// Number of command bytes sent to address the pages, without the control bytes or
+    /// the D/C line of the interface.
+    pub command_bytes: usize,
+}
+
+impl FlushStats {
+    /// Counts one page transfer. Transfers follow each other page by page, so a page is
+    /// counted when its address differs from the one of the previous transfer.
+    fn record(&mut self, commands: &CommandBuffer<2>, data: &[u8], last_page: &mut Option<Page>) {
+        let page = commands.iter().find_map(|command| match command {
+            Command::PageAddress(page) => Some(*page),
+            _ => None,
+        });
+        if page != *last_page {
+            self.pages_sent += 1;
+            *last_page = page;
+        }
+        self.data_bytes += data.len();
+        self.command_bytes += commands.iter().map(Command::get_byte_size).sum::<usize>();
+    }
+}
+
+/// Sends every page transfer through `communication_interface`, and returns the pages and
+/// bytes sent.
+fn write_pages<'a, CI: CommunicationInterface>(
+    pages: impl Iterator<Item = (CommandBuffer<2>, PageData<'a>)>,
+    communication_interface: &mut CI,
+) -> Result<FlushStats, MiniOledError> {
+    let mut stats = FlushStats::default();
+    let mut last_page = None;
+    for (commands, pixel_buffer) in pages {
+        communication_interface.write_command(&commands)?;
+        communication_interface.write_data(&pixel_buffer)?;
+        stats.record(&commands, &pixel_buffer, &mut last_page);
+    }
+    Ok(stats)
+}
+
+/// Asynchronously sends every page transfer like `write_pages`.
+#[cfg(feature = "async")]
+async fn write_pages_async<'a, CI: AsyncCommunicationInterface>(
+    pages: impl Iterator<Item = (CommandBuffer<2>, PageData<'a>)>,
+    communication_interface: &mut CI,
+) -> Result<FlushStats, MiniOledError> {
+    let mut stats = FlushStats::default();
+    let mut last_page = None;
+    for (commands, pixel_buffer) in pages {
+        communication_interface.write_command(&commands).await?;
+        communication_interface.write_data(&pixel_buffer).await?;
+        stats.record(&commands, &pixel_buffer, &mut last_page);
+    }
+    Ok(stats)
+}
+
+/// Bytes of one page transfer, as sent to the controller.
+pub(crate) enum PageData<'a> {
+    /// Borrowed from a buffer in the controller layout
+    Buffer(&'a [u8]),
+    /// Transposed from the logical layout of `RotationMode::FlushTime`, with the length used
+    Transposed([u8; RAM_COLUMNS], usize),
+}
+
+impl Deref for PageData<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            PageData::Buffer(bytes) => bytes,
+            PageData::Transposed(bytes, length) => &bytes[..*length],
+        }
+    }
+}
+
+/// Returns the commands addressing `column` of `page`, including the column offset.
+///
+/// Columns past the controller RAM, from a column offset too large for the panel, give an
+/// address that fails validation instead of wrapping around.
+fn page_commands(page: Page, column: u32) -> CommandBuffer<2> {
+    [
+        Command::PageAddress(page),
+        Command::ColumnAddress(u8::try_from(column).unwrap_or(u8::MAX)),
+    ]
+    .into()
+}
+
+/// Grows the column range of every page from `page_min` to `page_max` in `page_columns`.
+fn expand_page_columns(
+    page_columns: &mut [(u32, u32); PAGE_COUNT],
+    column_min: u32,
+    column_max: u32,
+    page_min: u32,
+    page_max: u32,
+) {
+    let pages = (page_min as usize).min(PAGE_COUNT)..(page_max as usize + 1).min(PAGE_COUNT);
+    for (page_column_min, page_column_max) in &mut page_columns[pages] {
+        *page_column_min = (*page_column_min).min(column_min);
+        *page_column_max = (*page_column_max).max(column_max);
+    }
+}
+
 #[cfg(feature = "embedded-graphics-core")]
 use embedded_graphics_core::{
     Pixel,
     pixelcolor::BinaryColor,
-    prelude::{Dimensions, DrawTarget, OriginDimensions, Size},
+    prelude::{Dimensions, DrawTarget, OriginDimensions, Point, Size},
+    primitives::Rectangle,
 };
 
 #[cfg(feature = "embedded-graphics-core")]
@@ -184,6 +1680,28 @@ impl<const N: usize, const W: u32, const H: u32, const O: u8> DrawTarget for Can
 
         Ok(())
     }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let clip = self.bounding_box();
+        self.fill_solid_clipped(area, &clip, color);
+        Ok(())
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let clip = self.bounding_box();
+        self.fill_contiguous_clipped(area, &clip, colors);
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.buffer.fill((-(color.is_on() as i8)) as u8);
+        self.force_full_dirty_area();
+
+        Ok(())
+    }
 }
 
 #[cfg(feature = "embedded-graphics-core")]
@@ -191,8 +1709,234 @@ impl<const N: usize, const W: u32, const H: u32, const O: u8> OriginDimensions
     for Canvas<N, W, H, O>
 {
     fn size(&self) -> Size {
-        let (width, height) = self.display_properties.get_display_size();
+        let (width, height) = self.get_rotated_size();
 
         Size::new(width, height)
     }
 }
+
+#[cfg(feature = "embedded-graphics-core")]
+impl<const N: usize, const W: u32, const H: u32, const O: u8> Canvas<N, W, H, O> {
+    /// Returns a `RegionCanvas` drawing into `area` only, for example to draw a widget
+    /// without it bleeding into its neighbours.
+    ///
+    /// The region has its own origin at the top left corner of `area`. Writes are clipped to
+    /// the region and marked dirty, without the per-pixel checks against the whole display
+    /// that `set_pixel` does. `area` is clipped to the display.
+    ///
+    /// # Arguments
+    ///
+    /// * `area` - The area of the canvas to draw into.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use embedded_graphics::{prelude::*, primitives::Rectangle};
+    ///
+    /// let mut region = canvas.region(Rectangle::new(Point::new(64, 0), Size::new(64, 16)));
+    /// // Clipped at the edge of the region, not of the display.
+    /// region.fill_solid(&Rectangle::new(Point::new(50, 0), Size::new(40, 8)), BinaryColor::On)?;
+    /// ```
+    pub fn region(&mut self, area: Rectangle) -> RegionCanvas<'_, N, W, H, O> {
+        let area = area.intersection(&self.bounding_box());
+        RegionCanvas { canvas: self, area }
+    }
+
+    /// Fills `area`, clipped to `clip`, which must lie inside the display.
+    fn fill_solid_clipped(&mut self, area: &Rectangle, clip: &Rectangle, color: BinaryColor) {
+        let area = area.intersection(clip);
+
+        if let Some(bottom_right) = area.bottom_right() {
+            self.fill_area(
+                (area.top_left.x as u32, area.top_left.y as u32),
+                (bottom_right.x as u32, bottom_right.y as u32),
+                color.is_on(),
+            );
+        }
+    }
+
+    /// Fills `area` row by row with `colors`, clipped to `clip`, which must lie inside the
+    /// display.
+    fn fill_contiguous_clipped<I>(&mut self, area: &Rectangle, clip: &Rectangle, colors: I)
+    where
+        I: IntoIterator<Item = BinaryColor>,
+    {
+        let drawable_area = area.intersection(clip);
+        let Some(bottom_right) = drawable_area.bottom_right() else {
+            return;
+        };
+        let top_left = drawable_area.top_left;
+        let min = (top_left.x as u32, top_left.y as u32);
+        let max = (bottom_right.x as u32, bottom_right.y as u32);
+
+        if drawable_area == *area
+            && matches!(
+                self.display_properties.get_rotation(),
+                DisplayRotation::Rotate0 | DisplayRotation::Rotate180
+            )
+        {
+            self.fill_contiguous_rows(min, max, colors.into_iter());
+            self.expand_dirty_area(min, max);
+            return;
+        }
+
+        let mut colors = colors.into_iter();
+        for y in area.rows() {
+            if y > bottom_right.y {
+                break;
+            }
+            if y < top_left.y {
+                // Skip the colors of the whole row.
+                if colors.nth(area.size.width as usize - 1).is_none() {
+                    break;
+                }
+                continue;
+            }
+
+            for (x, color) in area.columns().zip(colors.by_ref()) {
+                if x >= top_left.x && x <= bottom_right.x {
+                    self.write_pixel(x as u32, y as u32, color.is_on());
+                }
+            }
+        }
+
+        self.expand_dirty_area(min, max);
+    }
+
+    /// Fills the unclipped logical rectangle from `min` to `max` row by row with `colors`,
+    /// in a rotation where every logical row lies in a single page.
+    ///
+    /// This is the path of 1bpp images like `ImageRaw`. The page and bit of each row are
+    /// computed once, and a page aligned rectangle is cleared as whole bytes first, so only
+    /// the lit pixels are written.
+    fn fill_contiguous_rows(
+        &mut self,
+        min: (u32, u32),
+        max: (u32, u32),
+        mut colors: impl Iterator<Item = BinaryColor>,
+    ) {
+        let ((column_min, row_min), (column_max, row_max)) = self.get_physical_area(min, max);
+        let width = (max.0 - min.0 + 1) as usize;
+        let page_aligned = row_min.is_multiple_of(8) && (row_max + 1).is_multiple_of(8);
+
+        if page_aligned {
+            for page in (row_min >> 3)..=(row_max >> 3) {
+                let page_start_idx = fast_mul(page, W) as usize;
+                self.buffer
+                    [page_start_idx + column_min as usize..=page_start_idx + column_max as usize]
+                    .fill(0);
+            }
+        }
+
+        for y in min.1..=max.1 {
+            let (page, column, bit_mask) = self.get_pixel_position(min.0, y);
+            let row_start_idx = fast_mul(page, W) as usize;
+            // The row runs towards lower columns when the panel is mirrored.
+            let columns = (column_min as usize..=column_max as usize).map(|c| row_start_idx + c);
+            let row = colors.by_ref().take(width);
+
+            match (column == column_min, page_aligned) {
+                (true, true) => set_bits(&mut self.buffer, columns.zip(row), bit_mask),
+                (true, false) => write_bits(&mut self.buffer, columns.zip(row), bit_mask),
+                (false, true) => set_bits(&mut self.buffer, columns.rev().zip(row), bit_mask),
+                (false, false) => write_bits(&mut self.buffer, columns.rev().zip(row), bit_mask),
+            }
+        }
+    }
+}
+
+/// Sets `bit_mask` in every byte paired with a lit color, leaving the others untouched.
+#[cfg(feature = "embedded-graphics-core")]
+fn set_bits(buffer: &mut [u8], pixels: impl Iterator<Item = (usize, BinaryColor)>, bit_mask: u8) {
+    for (idx, color) in pixels {
+        if color.is_on() {
+            buffer[idx] |= bit_mask;
+        }
+    }
+}
+
+/// Writes `bit_mask` of every byte to the paired color.
+#[cfg(feature = "embedded-graphics-core")]
+fn write_bits(buffer: &mut [u8], pixels: impl Iterator<Item = (usize, BinaryColor)>, bit_mask: u8) {
+    for (idx, color) in pixels {
+        let pixel_status_mask = (-(color.is_on() as i8)) as u8;
+        buffer[idx] = (buffer[idx] & !bit_mask) | (pixel_status_mask & bit_mask);
+    }
+}
+
+/// A clipped, translated view of a `Canvas`, returned by `Canvas::region`.
+///
+/// It implements `DrawTarget` with its origin at the top left corner of the region, and
+/// drops everything outside of it.
+#[cfg(feature = "embedded-graphics-core")]
+pub struct RegionCanvas<'a, const N: usize, const W: u32, const H: u32, const O: u8> {
+    canvas: &'a mut Canvas<N, W, H, O>,
+    area: Rectangle,
+}
+
+#[cfg(feature = "embedded-graphics-core")]
+impl<const N: usize, const W: u32, const H: u32, const O: u8> DrawTarget
+    for RegionCanvas<'_, N, W, H, O>
+{
+    type Color = BinaryColor;
+
+    type Error = MiniOledError;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let mut drawn_min = Point::new(i32::MAX, i32::MAX);
+        let mut drawn_max = Point::new(i32::MIN, i32::MIN);
+
+        for Pixel(point, color) in pixels {
+            let point = point + self.area.top_left;
+            if self.area.contains(point) {
+                self.canvas
+                    .write_pixel(point.x as u32, point.y as u32, color.is_on());
+                drawn_min = drawn_min.component_min(point);
+                drawn_max = drawn_max.component_max(point);
+            }
+        }
+
+        if drawn_min.x <= drawn_max.x {
+            self.canvas.expand_dirty_area(
+                (drawn_min.x as u32, drawn_min.y as u32),
+                (drawn_max.x as u32, drawn_max.y as u32),
+            );
+        }
+
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = Rectangle::new(area.top_left + self.area.top_left, area.size);
+        self.canvas.fill_solid_clipped(&area, &self.area, color);
+        Ok(())
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let area = Rectangle::new(area.top_left + self.area.top_left, area.size);
+        self.canvas
+            .fill_contiguous_clipped(&area, &self.area, colors);
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.canvas
+            .fill_solid_clipped(&self.area, &self.area, color);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "embedded-graphics-core")]
+impl<const N: usize, const W: u32, const H: u32, const O: u8> OriginDimensions
+    for RegionCanvas<'_, N, W, H, O>
+{
+    fn size(&self) -> Size {
+        self.area.size
+    }
+}
@@ -1,7 +1,9 @@
 //! # Screen
 //!
 //! This module contains the screen-related definitions, including the `Canvas` for drawing,
-//! `DisplayProperties` for configuration, and the `Sh1106` driver implementation.
+//! `DisplayProperties` for configuration, the `Sh1106` driver implementation, the
+//! `Sh1106Builder` for custom initialization settings, and the `FrameTimer` and
+//! `DisplayStartLineAnimator` for animations.
 //!
 //! ## Example
 //!
@@ -24,19 +26,30 @@
 //! screen.flush().unwrap();
 //! ```
 
+pub mod animator;
+pub mod builder;
 pub mod canvas;
 pub mod properties;
 pub mod sh1106;
+#[cfg(feature = "text")]
+pub mod text;
+pub mod timer;
 
-macro_rules! fast_mul {
-    ($value:expr, $right:expr) => {{
-        let value_u32 = ($value) as u32;
-        if $right > 0 && ($right & ($right - 1)) == 0 {
-            value_u32 << $right.trailing_zeros()
-        } else {
-            value_u32 * $right
-        }
-    }};
+/// Multiplies `value` by `right`, with a shift instead when `right` is a power of two.
+///
+/// `right` is usually a const generic like the panel width, so the branch is resolved at
+/// compile time. The product must fit in a `u32`: buffer indices stay far below that, so an
+/// overflow is a bug. It panics in debug builds and wraps in release builds, for the shift
+/// as for the multiplication.
+#[inline]
+pub(crate) const fn fast_mul(value: u32, right: u32) -> u32 {
+    debug_assert!(
+        value.checked_mul(right).is_some(),
+        "fast_mul overflowed u32"
+    );
+    if right.is_power_of_two() {
+        value << right.trailing_zeros()
+    } else {
+        value.wrapping_mul(right)
+    }
 }
-
-pub(crate) use fast_mul;
@@ -14,6 +14,8 @@
 //! let rotation = DisplayRotation::Rotate90;
 //! ```
 
+use crate::command::Command;
+
 /// Properties of the display, such as dimensions and rotation.
 ///
 /// This struct is typically used internally by the `Sh1106` driver, but can be interacted with
@@ -31,25 +33,129 @@
 /// ```
 pub struct DisplayProperties<const W: u32, const H: u32, const O: u8> {
     display_rotation: DisplayRotation,
+    panel_rotation: DisplayRotation,
+    panel_mirror: (bool, bool),
+    controller: Controller,
+    column_offset: Option<u8>,
+    rotation_mode: RotationMode,
 }
 
 impl<const W: u32, const H: u32, const O: u8> DisplayProperties<W, H, O> {
     pub(crate) fn new(display_rotation: DisplayRotation) -> Self {
-        DisplayProperties { display_rotation }
+        DisplayProperties {
+            display_rotation,
+            panel_rotation: display_rotation,
+            panel_mirror: (false, false),
+            controller: Controller::Sh1106,
+            column_offset: None,
+            rotation_mode: RotationMode::PerPixel,
+        }
+    }
+
+    pub(crate) fn with_controller(mut self, controller: Controller) -> Self {
+        self.controller = controller;
+        self
+    }
+
+    /// Replaces the column offset derived from `O` and the controller, if `Some`.
+    pub(crate) fn with_column_offset(mut self, column_offset: Option<u8>) -> Self {
+        self.column_offset = column_offset;
+        self
     }
 
     pub(crate) fn set_rotation(&mut self, display_rotation: DisplayRotation) {
         self.display_rotation = display_rotation;
+        self.panel_rotation = display_rotation;
+    }
+
+    /// Changes the rotation of the coordinates only, keeping the rotation the segment remap
+    /// and COM scan direction of the panel are set for.
+    pub(crate) fn set_logical_rotation(&mut self, display_rotation: DisplayRotation) {
+        self.display_rotation = display_rotation;
+    }
+
+    /// Records the rotation the segment remap and COM scan direction of the panel are set for.
+    pub(crate) fn set_panel_rotation(&mut self, panel_rotation: DisplayRotation) {
+        self.panel_rotation = panel_rotation;
+    }
+
+    /// Records whether the panel columns and rows are mirrored in hardware on top of the
+    /// rotation. Both the expected and the actual panel state include it, so it doesn't
+    /// change the mirroring of the buffer.
+    pub(crate) fn set_panel_mirror(&mut self, panel_mirror: (bool, bool)) {
+        self.panel_mirror = panel_mirror;
+    }
+
+    pub(crate) fn get_panel_mirror(&self) -> (bool, bool) {
+        self.panel_mirror
+    }
+
+    /// Returns the segment remap and COM scan direction commands for the rotation the panel
+    /// is set for, with the mirroring of `set_panel_mirror` applied.
+    ///
+    /// After `set_logical_rotation` this is still the rotation of the last `set_rotation` or
+    /// `init`, which the software mirroring of `get_mirroring` corrects for.
+    pub(crate) fn panel_commands(&self) -> [Command; 2] {
+        self.rotation_commands(self.panel_rotation)
+    }
+
+    /// Returns the segment remap and COM scan direction commands that set the panel up for
+    /// the logical rotation, as sent by the initialization sequence.
+    pub(crate) fn init_panel_commands(&self) -> [Command; 2] {
+        self.rotation_commands(self.display_rotation)
+    }
+
+    fn rotation_commands(&self, rotation: DisplayRotation) -> [Command; 2] {
+        let (segment_remap, reverse_com) = rotation.panel_mirroring();
+        let (mirror_columns, mirror_rows) = self.panel_mirror;
+        [
+            match segment_remap != mirror_columns {
+                true => Command::EnableSegmentRemap,
+                false => Command::DisableSegmentRemap,
+            },
+            match reverse_com != mirror_rows {
+                true => Command::EnableReverseComDir,
+                false => Command::DisableReverseComDir,
+            },
+        ]
+    }
+
+    /// Returns whether the columns and the rows have to be mirrored in the buffer, because
+    /// the panel mirroring doesn't match the rotation.
+    pub(crate) fn get_mirroring(&self) -> (bool, bool) {
+        let (panel_segment_remap, panel_reverse_com) = self.panel_rotation.panel_mirroring();
+        let (segment_remap, reverse_com) = self.display_rotation.panel_mirroring();
+        (
+            panel_segment_remap != segment_remap,
+            panel_reverse_com != reverse_com,
+        )
     }
 
     pub(crate) fn get_column_offset(&self) -> u8 {
-        O
+        match (self.column_offset, self.controller) {
+            (Some(column_offset), _) => column_offset,
+            (None, Controller::Sh1106) => O,
+            // The SSD1306 RAM is 4 columns narrower, with the panel centered in both.
+            (None, Controller::Ssd1306) => O.saturating_sub(2),
+        }
     }
 
     pub(crate) fn get_rotation(&self) -> &DisplayRotation {
         &self.display_rotation
     }
 
+    pub(crate) fn get_controller(&self) -> Controller {
+        self.controller
+    }
+
+    pub(crate) fn set_rotation_mode(&mut self, rotation_mode: RotationMode) {
+        self.rotation_mode = rotation_mode;
+    }
+
+    pub(crate) fn get_rotation_mode(&self) -> RotationMode {
+        self.rotation_mode
+    }
+
     pub(crate) const fn get_display_size(&self) -> (u32, u32) {
         (W, H)
     }
@@ -57,9 +163,7 @@ impl<const W: u32, const H: u32, const O: u8> DisplayProperties<W, H, O> {
 
 impl<const W: u32, const H: u32, const O: u8> Default for DisplayProperties<W, H, O> {
     fn default() -> Self {
-        Self {
-            display_rotation: DisplayRotation::Rotate0,
-        }
+        Self::new(DisplayRotation::Rotate0)
     }
 }
 
@@ -72,7 +176,7 @@ impl<const W: u32, const H: u32, const O: u8> Default for DisplayProperties<W, H
 ///
 /// let rotation = DisplayRotation::Rotate90;
 /// ```
-#[derive(Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DisplayRotation {
     /// No rotation, normal display
     Rotate0,
@@ -83,3 +187,169 @@ pub enum DisplayRotation {
     /// Rotate 270 degress clockwise
     Rotate270,
 }
+
+impl DisplayRotation {
+    /// Returns the rotation for a clockwise angle in degrees, like the output of an
+    /// orientation sensor.
+    ///
+    /// Angles of a full turn or more wrap around, so `360` is `Rotate0`.
+    ///
+    /// # Returns
+    ///
+    /// The rotation, or `None` if `degrees` isn't a multiple of 90.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mini_oled::screen::properties::DisplayRotation;
+    ///
+    /// assert_eq!(DisplayRotation::from_degrees(270), Some(DisplayRotation::Rotate270));
+    /// assert_eq!(DisplayRotation::from_degrees(45), None);
+    /// ```
+    pub const fn from_degrees(degrees: u16) -> Option<Self> {
+        match degrees % 360 {
+            0 => Some(DisplayRotation::Rotate0),
+            90 => Some(DisplayRotation::Rotate90),
+            180 => Some(DisplayRotation::Rotate180),
+            270 => Some(DisplayRotation::Rotate270),
+            _ => None,
+        }
+    }
+
+    /// Returns the clockwise angle of the rotation in degrees, from 0-270.
+    pub const fn to_degrees(&self) -> u16 {
+        match self {
+            DisplayRotation::Rotate0 => 0,
+            DisplayRotation::Rotate90 => 90,
+            DisplayRotation::Rotate180 => 180,
+            DisplayRotation::Rotate270 => 270,
+        }
+    }
+
+    /// Returns whether the segment remap and the reverse COM scan direction are enabled for
+    /// this rotation, as sent by `Sh1106::set_rotation`.
+    pub(crate) fn panel_mirroring(&self) -> (bool, bool) {
+        match self {
+            DisplayRotation::Rotate0 => (true, true),
+            DisplayRotation::Rotate90 => (false, true),
+            DisplayRotation::Rotate180 => (false, false),
+            DisplayRotation::Rotate270 => (true, false),
+        }
+    }
+}
+
+/// Where the 90 and 270 degree rotations are applied, set with `Canvas::set_rotation_mode`.
+///
+/// # Example
+///
+/// ```rust
+/// use mini_oled::screen::properties::RotationMode;
+///
+/// let rotation_mode = RotationMode::FlushTime;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RotationMode {
+    /// The buffer uses the controller layout and every pixel is rotated when it is drawn
+    PerPixel,
+    /// The buffer uses the logical layout, which is transposed when the pages are flushed
+    FlushTime,
+}
+
+/// COM pin hardware configuration.
+///
+/// Most 128x64 panels use `Alternative`, some smaller panels need `Sequential`.
+///
+/// # Example
+///
+/// ```rust
+/// use mini_oled::screen::properties::ComPinConfig;
+///
+/// let com_pin_config = ComPinConfig::Sequential;
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub enum ComPinConfig {
+    /// Sequential COM pin configuration
+    Sequential,
+    /// Alternative COM pin configuration
+    Alternative,
+}
+
+/// Test patterns drawn by `Sh1106::draw_test_pattern` for panel bring-up.
+///
+/// Unlike `test_screen`, these patterns go through the display RAM, so they reveal wrong column
+/// offsets, flipped pages or dead segments.
+///
+/// # Example
+///
+/// ```rust
+/// use mini_oled::screen::properties::TestPattern;
+///
+/// let pattern = TestPattern::Checkerboard;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestPattern {
+    /// 8x8 pixel squares, aligned to the pages
+    Checkerboard,
+    /// One pixel wide vertical stripes, starting with a lit column
+    VerticalStripes,
+    /// One pixel wide outline around the edges of the display
+    Border,
+}
+
+/// Conversion of gray levels to lit and dark pixels, used by `Canvas::blit_gray`.
+///
+/// # Example
+///
+/// ```rust
+/// use mini_oled::screen::properties::DitherMode;
+///
+/// let dither_mode = DitherMode::Threshold(0x80);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DitherMode {
+    /// Lights every pixel at or above the given level
+    Threshold(u8),
+    /// Ordered dithering with a 4x4 Bayer matrix, which keeps gradients visible
+    Bayer4x4,
+}
+
+/// Combination of a 1 bit per pixel bitmap with the canvas, used by `Canvas::draw_bitmap`.
+///
+/// # Example
+///
+/// ```rust
+/// use mini_oled::screen::properties::BlitMode;
+///
+/// let blit_mode = BlitMode::Or;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlitMode {
+    /// Set bits light the pixel and clear bits turn it off
+    Replace,
+    /// Set bits light the pixel, clear bits are transparent
+    Or,
+    /// Clear bits turn the pixel off, set bits are transparent
+    And,
+    /// Set bits invert the pixel, clear bits are transparent
+    Xor,
+}
+
+/// Display controller of the module.
+///
+/// Many modules sold as SH1106 actually use an SSD1306, which has a narrower RAM and a
+/// different charge pump command. Selecting the wrong one shifts the image by two pixels.
+///
+/// # Example
+///
+/// ```rust
+/// use mini_oled::screen::properties::Controller;
+///
+/// let controller = Controller::Ssd1306;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Controller {
+    /// SH1106 controller with 132 columns of RAM
+    Sh1106,
+    /// SSD1306 controller with 128 columns of RAM
+    Ssd1306,
+}
@@ -19,16 +19,33 @@
 //! display.test_screen().unwrap();
 //! ```
 
+use embedded_hal::{
+    delay::DelayNs,
+    digital::{Error, OutputPin},
+};
+
 use crate::{
-    command::{Command, CommandBuffer, Page},
+    command::{
+        Command, CommandBuffer, HorizontalScrollDirection, NFrames, Page, PumpVoltage, VcomhLevel,
+    },
     error::MiniOledError,
-    interface::CommunicationInterface,
-    screen::fast_mul,
+    interface::{CommunicationInterface, MAX_COMMAND_BYTES},
 };
 
+#[cfg(feature = "async")]
+use crate::interface::AsyncCommunicationInterface;
+
+#[cfg(feature = "heapless")]
+use crate::command::DynCommandBuffer;
+
+#[cfg(feature = "text")]
+use crate::screen::text::TextCursor;
+
 use crate::screen::{
-    canvas::Canvas,
-    properties::{DisplayProperties, DisplayRotation},
+    builder::InitConfig,
+    canvas::{Canvas, FlushStats},
+    properties::{Controller, DisplayProperties, DisplayRotation, TestPattern},
+    timer::FrameTimer,
 };
 
 const WIDTH: u32 = 128;
@@ -36,9 +53,26 @@ const HEIGHT: u32 = 64;
 const OFFSET: u8 = 2;
 const BUFFER_SIZE: usize = WIDTH as usize * HEIGHT as usize / 8;
 
+const WIDTH_64X48: u32 = 64;
+const HEIGHT_64X48: u32 = 48;
+const OFFSET_64X48: u8 = 32;
+const BUFFER_SIZE_64X48: usize = WIDTH_64X48 as usize * HEIGHT_64X48 as usize / 8;
+
+/// Busy flag of the status byte read by `read_status`.
+const STATUS_BUSY: u8 = 0x80;
+/// Flag of the status byte read by `read_status`, set while the display is off.
+const STATUS_DISPLAY_OFF: u8 = 0x40;
+
+/// Settle time of the charge pump before the panel is configured, used by `init_with_delay`.
+const CHARGE_PUMP_DELAY_MS: u32 = 5;
+/// Time the panel takes to come up after it is turned on, used by `init_with_delay`.
+const DISPLAY_ON_DELAY_MS: u32 = 100;
+
 /// The main driver struct for the SH1106 OLED display.
 ///
 /// This struct manages the communication interface and the drawing canvas.
+/// The const generic parameters are the buffer size, width, height and column offset of the
+/// panel. They default to the common 128x64 panel, other panels have their own constructors.
 ///
 /// # Example
 ///
@@ -53,86 +87,565 @@ const BUFFER_SIZE: usize = WIDTH as usize * HEIGHT as usize / 8;
 /// screen.init().unwrap();
 /// screen.test_screen().unwrap();
 /// ```
-pub struct Sh1106<CI: CommunicationInterface> {
+pub struct Sh1106<
+    CI,
+    const N: usize = BUFFER_SIZE,
+    const W: u32 = WIDTH,
+    const H: u32 = HEIGHT,
+    const O: u8 = OFFSET,
+> {
     communication_interface: CI,
-    canvas: Canvas<BUFFER_SIZE, WIDTH, HEIGHT, OFFSET>,
+    canvas: Canvas<N, W, H, O>,
+    init_config: InitConfig,
+    contrast: u8,
+    inverted: bool,
+    display_on: bool,
 }
 
-impl<CI: CommunicationInterface> Sh1106<CI> {
-    /// Creates a new `Sh1106` driver instance.
+impl<CI> Sh1106<CI> {
+    /// Creates a new `Sh1106` driver instance for a 128x64 panel.
     ///
     /// # Arguments
     ///
-    /// * `communication_interface` - The initialized communication interface (I2C or ~~SPI~~).
+    /// * `communication_interface` - The initialized communication interface (I2C or SPI).
     pub fn new(communication_interface: CI) -> Sh1106<CI> {
-        let display_properties: DisplayProperties<WIDTH, HEIGHT, 2> =
-            DisplayProperties::new(DisplayRotation::Rotate0);
+        Self::with_init_config(communication_interface, InitConfig::default())
+    }
+
+    /// Creates a new `Sh1106` driver instance for a 128x64 panel in the given orientation.
+    ///
+    /// `init` sends the segment remap and COM scan direction of `display_rotation` directly,
+    /// so the display doesn't flip during startup as it does with `set_rotation` after `init`.
+    ///
+    /// # Arguments
+    ///
+    /// * `communication_interface` - The initialized communication interface (I2C or SPI).
+    /// * `display_rotation` - The rotation of the display.
+    pub fn new_with_rotation(
+        communication_interface: CI,
+        display_rotation: DisplayRotation,
+    ) -> Sh1106<CI> {
+        let mut sh1106 = Self::new(communication_interface);
+        sh1106.canvas.set_rotation(display_rotation);
+        sh1106
+    }
+}
+
+impl<CI> Sh1106<CI, BUFFER_SIZE_64X48, WIDTH_64X48, HEIGHT_64X48, OFFSET_64X48> {
+    /// Creates a new `Sh1106` driver instance for a 64x48 panel, like the Wemos D1 mini OLED shield.
+    ///
+    /// The visible area of these panels starts at column 32 of the controller RAM.
+    ///
+    /// # Arguments
+    ///
+    /// * `communication_interface` - The initialized communication interface (I2C or SPI).
+    pub fn new_64x48(communication_interface: CI) -> Self {
+        Self::with_init_config(communication_interface, InitConfig::default())
+    }
+}
+
+impl<CI, const N: usize, const W: u32, const H: u32, const O: u8> Sh1106<CI, N, W, H, O> {
+    /// Creates a new `Sh1106` driver instance for a panel of any size.
+    ///
+    /// The size is given by the const generic parameters, which are checked at compile time:
+    /// `N` must be `W * H / 8`, `H` must be a multiple of 8 from 16 to 64 and the panel must fit
+    /// in the 132 columns of the controller RAM, including the column offset `O`.
+    ///
+    /// # Arguments
+    ///
+    /// * `communication_interface` - The initialized communication interface (I2C or SPI).
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use mini_oled::screen::sh1106::Sh1106;
+    ///
+    /// // A 128x32 panel
+    /// let mut screen: Sh1106<_, 512, 128, 32, 2> = Sh1106::new_with_size(i2c_interface);
+    /// ```
+    ///
+    /// Since every combination is checked when the driver type is built, there is no
+    /// fallible constructor: an impossible size never reaches the running firmware. A buffer
+    /// size that doesn't match the panel fails to compile:
+    ///
+    /// ```rust,compile_fail
+    /// use mini_oled::screen::sh1106::Sh1106;
+    ///
+    /// let screen: Sh1106<(), 512, 128, 64, 2> = Sh1106::new_with_size(());
+    /// ```
+    ///
+    /// So do a height the controller can't drive and a panel that overflows the RAM with its
+    /// column offset:
+    ///
+    /// ```rust,compile_fail
+    /// use mini_oled::screen::sh1106::Sh1106;
+    ///
+    /// let screen: Sh1106<(), 576, 128, 36, 2> = Sh1106::new_with_size(());
+    /// ```
+    ///
+    /// ```rust,compile_fail
+    /// use mini_oled::screen::sh1106::Sh1106;
+    ///
+    /// let screen: Sh1106<(), 1024, 128, 64, 8> = Sh1106::new_with_size(());
+    /// ```
+    pub fn new_with_size(communication_interface: CI) -> Self {
+        Self::with_init_config(communication_interface, InitConfig::default())
+    }
+
+    pub(crate) fn with_init_config(communication_interface: CI, init_config: InitConfig) -> Self {
+        // `Canvas::new` checks the rest of the size, the multiplex ratio needs 16 rows.
+        const { assert!(H >= 16, "height must be a multiple of 8 from 16 to 64") };
+
+        let display_properties: DisplayProperties<W, H, O> =
+            DisplayProperties::new(DisplayRotation::Rotate0)
+                .with_controller(init_config.get_controller())
+                .with_column_offset(init_config.get_column_offset());
         Sh1106 {
             communication_interface,
             canvas: Canvas::new(display_properties),
+            contrast: init_config.get_contrast(),
+            init_config,
+            inverted: false,
+            display_on: false,
         }
     }
 
+    /// Consumes the driver and returns the underlying communication interface.
+    ///
+    /// This allows reusing the peripheral, for example to share the bus with other devices.
+    pub fn release(self) -> CI {
+        self.communication_interface
+    }
+
     /// Returns a reference to the underlying canvas.
-    pub fn get_canvas(&self) -> &Canvas<BUFFER_SIZE, WIDTH, HEIGHT, OFFSET> {
+    pub fn get_canvas(&self) -> &Canvas<N, W, H, O> {
         &self.canvas
     }
 
     /// Returns a mutable reference to the underlying canvas.
-    pub fn get_mut_canvas(&mut self) -> &mut Canvas<BUFFER_SIZE, WIDTH, HEIGHT, OFFSET> {
+    pub fn get_mut_canvas(&mut self) -> &mut Canvas<N, W, H, O> {
         &mut self.canvas
     }
 
-    /// Flushes the entire display buffer to the screen, refreshing all pixels.
-    pub fn flush_all(&mut self) -> Result<(), MiniOledError> {
+    /// Returns the inclusive bounding box `(min, max)` of the changes the next `flush` sends,
+    /// or `None` if nothing changed since the last flush.
+    ///
+    /// This allows skipping the flush entirely when nothing changed.
+    pub fn dirty_bounds(&self) -> Option<((u32, u32), (u32, u32))> {
+        if !self.canvas.is_dirty() {
+            return None;
+        }
+
+        Some(self.canvas.get_dirty_area())
+    }
+
+    /// Returns a `TextCursor` writing lit text on the canvas at `(x, y)`, for use with `write!`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use core::fmt::Write;
+    ///
+    /// write!(screen.text_cursor(0, 0), "Temp: {}C", 21).unwrap();
+    /// screen.flush().unwrap();
+    /// ```
+    #[cfg(feature = "text")]
+    pub fn text_cursor(&mut self, x: u32, y: u32) -> TextCursor<'_, N, W, H, O> {
+        self.canvas.text_cursor(x, y)
+    }
+
+    /// Returns the initialization sequence that `init` sends, with the settings of
+    /// `Sh1106Builder` and the current rotation applied.
+    ///
+    /// The sequence can be modified and sent with `init_with`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use mini_oled::command::Command;
+    ///
+    /// let mut sequence = screen.default_init();
+    /// sequence[9] = Command::Contrast(0x40);
+    /// screen.init_with(sequence)?;
+    /// ```
+    pub fn default_init(&self) -> [Command; 15] {
+        self.init_config.init_sequence(
+            self.canvas.get_display_size().1 as u8 - 1,
+            self.canvas.get_init_panel_commands(),
+        )
+    }
+
+    /// Records the state the initialization sequence leaves the display in.
+    fn record_init(&mut self) {
+        self.contrast = self.init_config.get_contrast();
+        self.inverted = false;
+        self.display_on = true;
+        // The initialization sequence sets the segment remap and COM direction of the rotation.
+        let display_rotation = *self.canvas.get_rotation();
+        self.canvas.set_panel_rotation(display_rotation);
+    }
+
+    /// Returns the logical `(width, height)` of the display for the current rotation, which is
+    /// the range of coordinates the canvas accepts.
+    pub fn dimensions(&self) -> (u32, u32) {
+        self.canvas.get_rotated_size()
+    }
+
+    /// Returns the native `(width, height)` of the panel, regardless of the rotation.
+    pub fn physical_dimensions(&self) -> (u32, u32) {
+        self.canvas.get_display_size()
+    }
+
+    /// Returns the current rotation of the display.
+    pub fn get_rotation(&self) -> &DisplayRotation {
+        self.canvas.get_rotation()
+    }
+
+    /// Sets the rotation of the display in software only, without sending any command.
+    ///
+    /// Unlike `set_rotation`, the segment remap and COM scan direction of the panel stay as
+    /// they are, so the content already on the display doesn't jump until the next flush.
+    /// The canvas mirrors the columns and rows itself where the panel doesn't match the new
+    /// rotation, so drawing gives the same image as after `set_rotation`. Use this for
+    /// frequent orientation changes, for example an accelerometer driven auto-rotate.
+    ///
+    /// The whole display is marked dirty, so redraw the content and `flush` to show it in
+    /// the new orientation.
+    ///
+    /// # Arguments
+    ///
+    /// * `display_rotation` - The new rotation setting.
+    pub fn set_logical_rotation(&mut self, display_rotation: DisplayRotation) {
+        self.canvas.set_logical_rotation(display_rotation);
         self.canvas.force_full_dirty_area();
-        self.flush()
     }
 
-    /// Flushes only the modified parts of the display buffer to the screen.
+    /// Returns the last contrast sent to the display.
+    pub fn get_contrast(&self) -> u8 {
+        self.contrast
+    }
+
+    /// Returns `true` if the display was last set to negative image mode.
+    pub fn is_inverted(&self) -> bool {
+        self.inverted
+    }
+
+    /// Returns `true` if the display was last turned on, by `init` or `set_display_on`.
+    pub fn is_display_on(&self) -> bool {
+        self.display_on
+    }
+}
+
+impl<CI: CommunicationInterface, const N: usize, const W: u32, const H: u32, const O: u8>
+    Sh1106<CI, N, W, H, O>
+{
+    /// Flushes the entire display buffer to the screen, refreshing all pixels.
     ///
-    /// This is more efficient than `flush_all` as it only sends changed data.
-    pub fn flush(&mut self) -> Result<(), MiniOledError> {
-        let ((dirty_min_x, dirty_min_y), (dirty_max_x, dirty_max_y)) = self.canvas.get_dirty_area();
+    /// Every page is sent over the full width without looking at the dirty area, which
+    /// makes this the fastest path when the whole frame is redrawn anyway.
+    pub fn flush_all(&mut self) -> Result<(), MiniOledError> {
+        for (commands, page_buffer) in self.canvas.full_pages(CI::MAX_DATA_BYTES) {
+            self.communication_interface.write_command(&commands)?;
+            self.communication_interface.write_data(&page_buffer)?;
+        }
+        self.canvas.reset_dirty_area();
+
+        #[cfg(feature = "diff")]
+        self.canvas.commit_frame();
+        Ok(())
+    }
 
-        if dirty_min_x > dirty_max_x || dirty_min_y > dirty_max_y {
-            return Ok(());
+    /// Flushes only the bytes that differ from the previously flushed frame.
+    ///
+    /// Unlike `flush`, which sends the bounding box of the changes, this compares every byte
+    /// against a copy of the last frame and sends each run of changed bytes, which is much
+    /// less data for small scattered changes. The copy costs `N` bytes of extra RAM
+    /// (1KB for a 128x64 panel) and is only available with the `diff` feature.
+    ///
+    /// The copy is updated by `flush_diff` and `flush_all` only, call `flush_all` once after
+    /// `init` so it matches the display.
+    #[cfg(feature = "diff")]
+    pub fn flush_diff(&mut self) -> Result<(), MiniOledError> {
+        for (commands, changed_buffer) in self.canvas.changed_runs(CI::MAX_DATA_BYTES) {
+            self.communication_interface.write_command(&commands)?;
+            self.communication_interface.write_data(&changed_buffer)?;
         }
 
-        let start_page = Page::from((dirty_min_y >> 3) as u8);
-        let end_page = Page::from((dirty_max_y >> 3) as u8);
+        self.canvas.commit_frame();
+        self.canvas.reset_dirty_area();
+        Ok(())
+    }
 
-        let pixel_buffer = self.canvas.get_buffer();
+    /// Flushes only the modified parts of the display buffer to the screen.
+    ///
+    /// This is more efficient than `flush_all` as it only sends changed data. Pages wider
+    /// than the `MAX_DATA_BYTES` of the interface are sent in several chunks, each
+    /// addressing its own first column.
+    ///
+    /// When nothing changed, this returns right away without touching the interface, so it
+    /// can be called on every pass of a polling loop.
+    ///
+    /// # Returns
+    ///
+    /// The number of display data bytes sent, `0` if nothing changed.
+    pub fn flush(&mut self) -> Result<usize, MiniOledError> {
+        self.canvas.flush_to(&mut self.communication_interface)
+    }
 
-        for page in Page::range(start_page, end_page) {
-            let page_start_idx = fast_mul!(page, WIDTH) + dirty_min_x;
-            let page_end_idx = fast_mul!(page, WIDTH) + dirty_max_x;
+    /// Flushes the modified parts of the display buffer like `flush`, and returns the pages,
+    /// data bytes and command bytes sent.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let stats = screen.flush_detailed()?;
+    /// // Compare stats.data_bytes with and without the diff feature, for example.
+    /// ```
+    pub fn flush_detailed(&mut self) -> Result<FlushStats, MiniOledError> {
+        self.canvas
+            .flush_detailed_to(&mut self.communication_interface)
+    }
 
-            if page_end_idx as usize >= pixel_buffer.len() {
-                break;
-            }
+    /// Reads the status byte of the controller.
+    ///
+    /// Bit 7 is the busy flag and bit 6 is set while the display is off. Only interfaces that
+    /// can read, like I2C, support it, others return `MiniOledError::UnsupportedOperation`.
+    pub fn read_status(&mut self) -> Result<u8, MiniOledError> {
+        self.communication_interface.read_status()
+    }
 
-            let dirty_pixel_buffer = &pixel_buffer[page_start_idx as usize..=page_end_idx as usize];
-            let current_column = dirty_min_x + self.canvas.get_column_offset() as u32;
-            let commands: CommandBuffer<3> = [
-                Command::PageAddress(page),
-                Command::ColumnAddressLow(current_column as u8),
-                Command::ColumnAddressHigh((current_column >> 4) as u8),
-            ]
-            .into();
+    /// Returns `true` while the controller is busy, from the busy flag of the status byte.
+    ///
+    /// Polling this during bring-up shows whether the controller responds, and careful
+    /// drivers can use it instead of fixed delays.
+    pub fn is_busy(&mut self) -> Result<bool, MiniOledError> {
+        Ok(self.read_status()? & STATUS_BUSY != 0)
+    }
 
+    /// Runs `draw` on the canvas, then flushes the changes like `flush`.
+    ///
+    /// The flush can't be forgotten, and only the area changed by `draw` is sent.
+    ///
+    /// # Arguments
+    ///
+    /// * `draw` - The drawing code, its return value is passed through.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// screen.modify_region(|canvas| {
+    ///     canvas.draw_hline(0, 10, 128, true);
+    ///     canvas.set_pixel(64, 32, true);
+    /// })?;
+    /// ```
+    pub fn modify_region<R>(
+        &mut self,
+        draw: impl FnOnce(&mut Canvas<N, W, H, O>) -> R,
+    ) -> Result<R, MiniOledError> {
+        let result = draw(&mut self.canvas);
+        self.flush()?;
+        Ok(result)
+    }
+
+    /// Flushes the changes like `flush`, then waits one frame interval of `timer`.
+    ///
+    /// Calling this once per frame gives an animation loop a steady frame rate.
+    ///
+    /// # Arguments
+    ///
+    /// * `delay` - The delay provider.
+    /// * `timer` - The frame timer giving the interval to wait.
+    pub fn flush_paced<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        timer: &FrameTimer,
+    ) -> Result<(), MiniOledError> {
+        self.flush()?;
+        timer.wait(delay);
+        Ok(())
+    }
+
+    /// Flushes only the pages and columns covering a rectangle, whether it changed or not.
+    ///
+    /// The tracked dirty area is left untouched, so the next `flush` still sends every other
+    /// change. This is useful for latency-sensitive updates like a blinking cursor.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The left edge of the rectangle.
+    /// * `y` - The top edge of the rectangle.
+    /// * `w` - The width of the rectangle.
+    /// * `h` - The height of the rectangle.
+    pub fn flush_region(&mut self, x: u32, y: u32, w: u32, h: u32) -> Result<(), MiniOledError> {
+        for (commands, pixel_buffer) in self.canvas.region_pages(x, y, w, h, CI::MAX_DATA_BYTES) {
             self.communication_interface.write_command(&commands)?;
-            self.communication_interface
-                .write_data(dirty_pixel_buffer)?;
+            self.communication_interface.write_data(&pixel_buffer)?;
         }
 
-        self.canvas.reset_dirty_area();
         Ok(())
     }
 
-    /// Returns the current rotation of the display.
-    pub fn get_rotation(&self) -> &DisplayRotation {
-        self.canvas.get_rotation()
+    /// Sets the display contrast. Higher values are brighter.
+    ///
+    /// # Arguments
+    ///
+    /// * `contrast` - The new contrast, from 0-255.
+    pub fn set_contrast(&mut self, contrast: u8) -> Result<(), MiniOledError> {
+        self.communication_interface
+            .write_command(&CommandBuffer::from(Command::Contrast(contrast)))?;
+        self.contrast = contrast;
+        Ok(())
+    }
+
+    /// Sets the brightness as a percentage of the perceived maximum, for brightness sliders.
+    ///
+    /// The segment current, and so the luminance, is roughly proportional to the contrast, but
+    /// the eye perceives luminance on a curve close to a gamma of 2.2. The contrast follows the
+    /// quadratic curve `255 * (percent / 100)²`, rounded, so equal steps of `percent` look like
+    /// equal changes in brightness. 0% is the dimmest level, not off. Use `set_contrast` to set
+    /// the raw value.
+    ///
+    /// # Arguments
+    ///
+    /// * `percent` - The brightness, from 0-100. Larger values are clamped to 100.
+    pub fn set_brightness(&mut self, percent: u8) -> Result<(), MiniOledError> {
+        let percent = u32::from(percent.min(100));
+        let contrast = (255 * percent * percent + 5_000) / 10_000;
+        self.set_contrast(contrast as u8)
+    }
+
+    /// Switches between positive (`false`) and negative (`true`) image mode.
+    ///
+    /// In negative image mode a cleared pixel is lit and a set pixel is dark. The RAM is
+    /// not changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `inverted` - `true` to invert the display.
+    pub fn set_inverted(&mut self, inverted: bool) -> Result<(), MiniOledError> {
+        let command = match inverted {
+            true => Command::NegativeImageMode,
+            false => Command::PositiveImageMode,
+        };
+        self.communication_interface
+            .write_command(&CommandBuffer::from(command))?;
+        self.inverted = inverted;
+        Ok(())
+    }
+
+    /// Turns the display on, or puts it into sleep mode.
+    ///
+    /// The RAM content is preserved in sleep mode, so no flush is needed after waking up.
+    ///
+    /// # Arguments
+    ///
+    /// * `on` - `true` to turn the display on, `false` to put it to sleep.
+    pub fn set_display_on(&mut self, on: bool) -> Result<(), MiniOledError> {
+        let command = match on {
+            true => Command::TurnDisplayOn,
+            false => Command::TurnDisplayOff,
+        };
+        self.communication_interface
+            .write_command(&CommandBuffer::from(command))?;
+        self.display_on = on;
+        Ok(())
+    }
+
+    /// Puts the display into deep sleep, turning off the display and then the charge pump.
+    ///
+    /// The display alone draws less than 20µA in sleep mode, turning off the charge pump as well
+    /// brings the controller down to its sleep current of a few µA, which matters on battery
+    /// devices. The RAM content is preserved, so no flush is needed after `wake`.
+    pub fn sleep(&mut self) -> Result<(), MiniOledError> {
+        let sequence: CommandBuffer<2> = [
+            Command::TurnDisplayOff,
+            self.init_config.charge_pump_command(false),
+        ]
+        .into();
+        self.communication_interface.write_command(&sequence)?;
+        self.display_on = false;
+        Ok(())
+    }
+
+    /// Wakes the display up from `sleep`.
+    ///
+    /// The charge pump is enabled before the display is turned on, as the controller requires.
+    /// It stays off if it was disabled with `Sh1106Builder::charge_pump`.
+    pub fn wake(&mut self) -> Result<(), MiniOledError> {
+        let sequence: CommandBuffer<2> = [
+            self.init_config.charge_pump_command(true),
+            Command::TurnDisplayOn,
+        ]
+        .into();
+        self.communication_interface.write_command(&sequence)?;
+        self.display_on = true;
+        Ok(())
+    }
+
+    /// Sends a sequence of commands in a single write.
+    ///
+    /// The buffer size is inferred from the array. Since every command takes at least one byte,
+    /// more than `MAX_COMMAND_BYTES` commands are rejected at compile time. Longer commands
+    /// can still exceed the limit, which returns `MiniOledError::CommandBufferSizeError`.
+    ///
+    /// # Arguments
+    ///
+    /// * `commands` - The commands to send, in order.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use mini_oled::command::Command;
+    ///
+    /// screen.send_commands([Command::NegativeImageMode, Command::Contrast(0x20)])?;
+    /// ```
+    pub fn send_commands<const M: usize>(
+        &mut self,
+        commands: [Command; M],
+    ) -> Result<(), MiniOledError> {
+        const {
+            assert!(
+                M > 0 && M <= MAX_COMMAND_BYTES,
+                "a command sequence must hold 1 to MAX_COMMAND_BYTES commands"
+            )
+        };
+
+        self.communication_interface
+            .write_command(&CommandBuffer::from(commands))
+    }
+
+    /// Sends a sequence of commands built at runtime.
+    ///
+    /// The whole sequence is validated first, then every command is sent in its own write,
+    /// since the interfaces take buffers sized at compile time. Use `send_commands` when the
+    /// commands are known in advance, which sends them in a single write.
+    ///
+    /// # Arguments
+    ///
+    /// * `commands` - The commands to send, in order.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use mini_oled::command::{Command, DynCommandBuffer};
+    ///
+    /// let mut commands: DynCommandBuffer<4> = DynCommandBuffer::new();
+    /// if dimmed {
+    ///     commands.push(Command::Contrast(0x10))?;
+    /// }
+    /// commands.push(Command::TurnDisplayOn)?;
+    /// screen.send_dyn_commands(&commands)?;
+    /// ```
+    #[cfg(feature = "heapless")]
+    pub fn send_dyn_commands<const CAP: usize>(
+        &mut self,
+        commands: &DynCommandBuffer<CAP>,
+    ) -> Result<(), MiniOledError> {
+        commands.validate()?;
+        commands.iter().try_for_each(|&command| {
+            self.communication_interface
+                .write_command(&CommandBuffer::from(command))
+        })
     }
 
     /// Enables the test screen mode (all pixels on).
@@ -142,7 +655,253 @@ impl<CI: CommunicationInterface> Sh1106<CI> {
         self.communication_interface.write_command(command_buffer)
     }
 
-    /// Sets the rotation of the display.
+    /// Draws a test pattern into the display RAM and flushes it.
+    ///
+    /// This overwrites the canvas. Unlike `test_screen`, the pattern goes through the normal
+    /// addressing path, which makes it useful to check the column offset and page order of a
+    /// new panel.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - The pattern to draw.
+    pub fn draw_test_pattern(&mut self, pattern: TestPattern) -> Result<(), MiniOledError> {
+        self.canvas.draw_test_pattern(pattern);
+        self.flush_all()
+    }
+
+    /// Ramps the contrast from `from` to `to`, sending one `Contrast` command per step.
+    ///
+    /// This crate has no timer, so the steps are sent back to back. Use `fade_with_delay` to
+    /// wait between steps, or call this repeatedly with small ranges from your own loop.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The first contrast value sent.
+    /// * `to` - The last contrast value sent.
+    /// * `step` - The contrast change between two commands, must not be 0.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` on success, or `MiniOledError::InvalidCommandArgument` if `step` is 0.
+    pub fn fade(&mut self, from: u8, to: u8, step: u8) -> Result<(), MiniOledError> {
+        self.fade_steps(from, to, step, || {})
+    }
+
+    /// Ramps the contrast from `from` to `to` like `fade`, waiting `step_delay_ms` milliseconds
+    /// between two steps.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The first contrast value sent.
+    /// * `to` - The last contrast value sent.
+    /// * `step` - The contrast change between two commands, must not be 0.
+    /// * `delay` - The delay provider.
+    /// * `step_delay_ms` - The time to wait between two steps, in milliseconds.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` on success, or `MiniOledError::InvalidCommandArgument` if `step` is 0.
+    pub fn fade_with_delay<D: DelayNs>(
+        &mut self,
+        from: u8,
+        to: u8,
+        step: u8,
+        delay: &mut D,
+        step_delay_ms: u32,
+    ) -> Result<(), MiniOledError> {
+        self.fade_steps(from, to, step, || delay.delay_ms(step_delay_ms))
+    }
+
+    fn fade_steps(
+        &mut self,
+        from: u8,
+        to: u8,
+        step: u8,
+        mut between_steps: impl FnMut(),
+    ) -> Result<(), MiniOledError> {
+        if step == 0 {
+            return Err(MiniOledError::InvalidCommandArgument);
+        }
+
+        let mut contrast = from;
+        loop {
+            self.set_contrast(contrast)?;
+            if contrast == to {
+                return Ok(());
+            }
+
+            between_steps();
+            contrast = match from < to {
+                true => contrast.saturating_add(step).min(to),
+                false => contrast.saturating_sub(step).max(to),
+            };
+        }
+    }
+
+    /// Sets the display start line, which shifts the RAM row shown at the top of the display.
+    ///
+    /// Changing it step by step scrolls the content vertically without rewriting the RAM.
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - The RAM row shown at the top, from 0-63.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` on success, or `MiniOledError::InvalidCommandArgument` if `line` is out of range.
+    pub fn set_start_line(&mut self, line: u8) -> Result<(), MiniOledError> {
+        if line > 63 {
+            return Err(MiniOledError::InvalidCommandArgument);
+        }
+
+        self.communication_interface
+            .write_command(&CommandBuffer::from(Command::StartLine(line)))
+    }
+
+    /// Sets the vertical display offset, which shifts the COM line mapped to the top row.
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - The vertical offset in rows, from 0-63.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` on success, or `MiniOledError::InvalidCommandArgument` if `offset` is out of range.
+    pub fn set_display_offset(&mut self, offset: u8) -> Result<(), MiniOledError> {
+        if offset > 63 {
+            return Err(MiniOledError::InvalidCommandArgument);
+        }
+
+        self.communication_interface
+            .write_command(&CommandBuffer::from(Command::DisplayOffset(offset)))
+    }
+
+    /// Sets the Vcomh deselect level, which affects the contrast uniformity of the panel.
+    ///
+    /// # Arguments
+    ///
+    /// * `level` - The new Vcomh deselect level.
+    pub fn set_vcomh(&mut self, level: VcomhLevel) -> Result<(), MiniOledError> {
+        self.communication_interface
+            .write_command(&CommandBuffer::from(Command::VcomhDeselect(level)))
+    }
+
+    /// Sets the output voltage of the SH1106 charge pump.
+    ///
+    /// Raising it makes the panel brighter, for example on 3.3V-only boards where the default
+    /// level is too dim.
+    ///
+    /// # Arguments
+    ///
+    /// * `voltage` - The new charge pump output voltage.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` on success, or `MiniOledError::UnsupportedOperation` without sending anything
+    /// if `Controller::Ssd1306` is selected, which has no such command.
+    pub fn set_pump_voltage(&mut self, voltage: PumpVoltage) -> Result<(), MiniOledError> {
+        if self.canvas.get_controller() != Controller::Sh1106 {
+            return Err(MiniOledError::UnsupportedOperation);
+        }
+
+        self.communication_interface
+            .write_command(&CommandBuffer::from(Command::PumpVoltage(voltage)))
+    }
+
+    /// Starts a continuous diagonal scroll of the whole display.
+    ///
+    /// The pages from `start_page` to `end_page` move horizontally in `direction` and every
+    /// row moves up by `vertical_offset` rows on each step, wrapping around. The controller
+    /// does the scrolling, so the content keeps moving without any flush. Directions are in
+    /// panel coordinates and ignore the rotation.
+    ///
+    /// Continuous scrolling is a feature of SSD1306 controllers. The SH1106 has none of these
+    /// opcodes and would decode their arguments as other commands, so this returns
+    /// `MiniOledError::UnsupportedOperation` without sending anything unless
+    /// `Controller::Ssd1306` is selected. Send `Command::DisableScroll` and flush the whole
+    /// buffer to stop it.
+    ///
+    /// # Arguments
+    ///
+    /// * `direction` - The horizontal scroll direction.
+    /// * `start_page` - The first page that scrolls horizontally.
+    /// * `end_page` - The last page that scrolls horizontally.
+    /// * `interval` - The interval between scroll steps in frames.
+    /// * `vertical_offset` - The rows moved vertically on each step, from 0-63.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use mini_oled::command::{HorizontalScrollDirection, NFrames, Page};
+    ///
+    /// screen.scroll_diagonal(HorizontalScrollDirection::Left, Page::Page0, Page::Page7, NFrames::F5, 1)?;
+    /// ```
+    pub fn scroll_diagonal(
+        &mut self,
+        direction: HorizontalScrollDirection,
+        start_page: Page,
+        end_page: Page,
+        interval: NFrames,
+        vertical_offset: u8,
+    ) -> Result<(), MiniOledError> {
+        if self.canvas.get_controller() != Controller::Ssd1306 {
+            return Err(MiniOledError::UnsupportedOperation);
+        }
+
+        self.send_commands([
+            Command::DisableScroll,
+            Command::VerticalScrollArea {
+                top_fixed_rows: 0,
+                scroll_rows: H as u8,
+            },
+            Command::VerticalAndHorizontalScrollSetup {
+                direction,
+                start_page,
+                interval,
+                end_page,
+                vertical_offset,
+            },
+            Command::EnableScroll,
+        ])
+    }
+
+    /// Sets phase 1 and 2 of the precharge period, in display clocks.
+    ///
+    /// Tuning these reduces flicker and ghosting on some panels.
+    ///
+    /// # Arguments
+    ///
+    /// * `phase1` - The precharge period, from 0-15.
+    /// * `phase2` - The discharge period, from 0-15.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` on success, or `MiniOledError::InvalidCommandArgument` if a phase is out of range.
+    pub fn set_precharge(&mut self, phase1: u8, phase2: u8) -> Result<(), MiniOledError> {
+        if phase1 > 0xF || phase2 > 0xF {
+            return Err(MiniOledError::InvalidCommandArgument);
+        }
+
+        self.communication_interface
+            .write_command(&CommandBuffer::from(Command::PreChargePeriod(
+                phase1, phase2,
+            )))
+    }
+
+    /// Sets the rotation of the display, mirroring the panel in hardware.
+    ///
+    /// The segment remap and COM scan direction are sent right away, which also flips the
+    /// content already on the display until the next flush:
+    ///
+    /// | Rotation    | Segment remap          | COM scan direction       |
+    /// |-------------|------------------------|--------------------------|
+    /// | `Rotate0`   | `EnableSegmentRemap`   | `EnableReverseComDir`    |
+    /// | `Rotate90`  | `DisableSegmentRemap`  | `EnableReverseComDir`    |
+    /// | `Rotate180` | `DisableSegmentRemap`  | `DisableReverseComDir`   |
+    /// | `Rotate270` | `EnableSegmentRemap`   | `DisableReverseComDir`   |
+    ///
+    /// `Rotate90` and `Rotate270` additionally swap the axes in software. To change the
+    /// rotation without sending these commands, use `set_logical_rotation`.
     ///
     /// # Arguments
     ///
@@ -150,47 +909,286 @@ impl<CI: CommunicationInterface> Sh1106<CI> {
     pub fn set_rotation(&mut self, display_rotation: DisplayRotation) -> Result<(), MiniOledError> {
         self.canvas.set_rotation(display_rotation);
 
-        let rotation_sequence: CommandBuffer<2> = match display_rotation {
-            DisplayRotation::Rotate0 => [Command::EnableSegmentRemap, Command::EnableReverseComDir],
-            DisplayRotation::Rotate90 => {
-                [Command::DisableSegmentRemap, Command::EnableReverseComDir]
-            }
-            DisplayRotation::Rotate180 => {
-                [Command::DisableSegmentRemap, Command::DisableReverseComDir]
-            }
-            DisplayRotation::Rotate270 => {
-                [Command::EnableSegmentRemap, Command::DisableReverseComDir]
-            }
-        }
-        .into();
+        let rotation_sequence = CommandBuffer::from(self.canvas.get_panel_commands());
 
         self.communication_interface
             .write_command(&rotation_sequence)
     }
 
-    /// Initializes the display with default settings.
+    /// Mirrors the panel horizontally in hardware, independently of the rotation.
+    ///
+    /// This is for panels mounted mirrored, for example seen through a reflector or a prism.
+    /// The segment remap is toggled relative to the rotation, so `Rotate0` stays `Rotate0`
+    /// instead of becoming a 180 degree rotation with one axis flipped back. The direction is
+    /// the one of the panel, which is the vertical axis in `Rotate90` and `Rotate270`. After
+    /// `set_logical_rotation`, it is toggled relative to the rotation the panel is still set for.
+    ///
+    /// The flip is done by the controller, so the canvas coordinates keep matching what is
+    /// seen. The setting is kept by `set_rotation` and `init`. The whole display is marked
+    /// dirty, since SSD1306 controllers only apply the segment remap to data written after it.
+    ///
+    /// # Arguments
     ///
-    /// This sends a sequence of commands to set up the display driver.
+    /// * `mirrored` - `true` to mirror the columns of the panel.
+    pub fn set_mirror_x(&mut self, mirrored: bool) -> Result<(), MiniOledError> {
+        let (_, mirror_rows) = self.canvas.get_panel_mirror();
+        self.canvas.set_panel_mirror((mirrored, mirror_rows));
+
+        let [segment_remap, _] = self.canvas.get_panel_commands();
+        self.communication_interface
+            .write_command(&CommandBuffer::from(segment_remap))?;
+        self.canvas.force_full_dirty_area();
+        Ok(())
+    }
+
+    /// Mirrors the panel vertically in hardware, independently of the rotation.
+    ///
+    /// Like `set_mirror_x`, but toggles the COM scan direction, which flips the rows of the
+    /// panel right away.
+    ///
+    /// # Arguments
+    ///
+    /// * `mirrored` - `true` to mirror the rows of the panel.
+    pub fn set_mirror_y(&mut self, mirrored: bool) -> Result<(), MiniOledError> {
+        let (mirror_columns, _) = self.canvas.get_panel_mirror();
+        self.canvas.set_panel_mirror((mirror_columns, mirrored));
+
+        let [_, com_scan_direction] = self.canvas.get_panel_commands();
+        self.communication_interface
+            .write_command(&CommandBuffer::from(com_scan_direction))
+    }
+
+    /// Resets the controller with its RST pin.
+    ///
+    /// Many modules need this at power-up, otherwise the controller may start in an undefined
+    /// state. Call it before `init`. The pin is held low for 10µs, which is the minimum reset
+    /// pulse of the controller, and the controller is given 1ms to come back up.
+    ///
+    /// # Arguments
+    ///
+    /// * `reset_pin` - The output pin connected to the RST line of the display.
+    /// * `delay` - The delay provider.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` on success, or `MiniOledError::PinError` if the pin can't be set.
+    pub fn reset<RST: OutputPin, D: DelayNs>(
+        &mut self,
+        reset_pin: &mut RST,
+        delay: &mut D,
+    ) -> Result<(), MiniOledError> {
+        let pin_error = |e: RST::Error| MiniOledError::PinError(e.kind());
+
+        reset_pin.set_high().map_err(pin_error)?;
+        delay.delay_ms(1);
+        reset_pin.set_low().map_err(pin_error)?;
+        delay.delay_us(10);
+        reset_pin.set_high().map_err(pin_error)?;
+        delay.delay_ms(1);
+
+        Ok(())
+    }
+
+    /// Initializes the display.
+    ///
+    /// This sends a sequence of commands to set up the display driver, using the default
+    /// settings or the ones configured with `Sh1106Builder`. The segment remap and COM scan
+    /// direction match the current rotation.
+    ///
+    /// The sequence is validated before anything is sent, whatever the interface, so a
+    /// setting the controller would ignore, like a multiplex ratio below 15, returns
+    /// `MiniOledError::InvalidCommandArgument` instead of leaving the display blank.
     pub fn init(&mut self) -> Result<(), MiniOledError> {
-        let init_sequence: CommandBuffer<15> = [
-            Command::TurnDisplayOff,
-            Command::DisplayClockDiv(0x8, 0x0),
-            Command::Multiplex(self.canvas.get_display_size().1 as u8 - 1),
-            Command::DisplayOffset(0),
-            Command::StartLine(0),
-            Command::EnableChargePump,
-            Command::EnableSegmentRemap,
-            Command::EnableReverseComDir,
-            Command::AlternativeComPinConfig,
-            Command::Contrast(0x80),
-            Command::PreChargePeriod(0x1, 0xF),
-            Command::VcomhDeselect(crate::command::VcomhLevel::Auto),
-            Command::DisableTestScreen,
-            Command::PositiveImageMode,
-            Command::TurnDisplayOn,
-        ]
-        .into();
+        let init_sequence = CommandBuffer::from(self.default_init());
+        init_sequence.validate()?;
+
+        self.communication_interface.write_command(&init_sequence)?;
+        self.record_init();
+        Ok(())
+    }
 
-        self.communication_interface.write_command(&init_sequence)
+    /// Initializes the display like `init`, then reads the status byte back to check that
+    /// the display turned on.
+    ///
+    /// This turns a panel that silently ignored the sequence, like a dead or unpowered one
+    /// that still acknowledges the bus, into an error. Only interfaces that can read, like
+    /// I2C, support it, others return `MiniOledError::UnsupportedOperation` after sending
+    /// the sequence.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` on success, or `MiniOledError::InitializationError` if the status byte still
+    /// reports the display as off.
+    pub fn init_verified(&mut self) -> Result<(), MiniOledError> {
+        self.init()?;
+        match self.read_status()? & STATUS_DISPLAY_OFF {
+            0 => Ok(()),
+            _ => Err(MiniOledError::InitializationError),
+        }
+    }
+
+    /// Initializes the display like `init`, waiting after the commands that need time to take
+    /// effect.
+    ///
+    /// The sequence is sent in three writes, with a short delay after the charge pump is
+    /// enabled and a 100ms delay after the display is turned on. Some clone controllers stay
+    /// blank without these delays, compliant panels only need `init`.
+    ///
+    /// # Arguments
+    ///
+    /// * `delay` - The delay provider.
+    pub fn init_with_delay<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), MiniOledError> {
+        let init_sequence = self.default_init();
+        CommandBuffer::from(init_sequence).validate()?;
+
+        let [
+            display_off,
+            clock_div,
+            multiplex,
+            display_offset,
+            start_line,
+            charge_pump,
+            segment_remap,
+            com_scan_direction,
+            com_pin_config,
+            contrast,
+            precharge_period,
+            vcomh_deselect,
+            test_screen,
+            image_mode,
+            display_on,
+        ] = init_sequence;
+
+        self.send_commands([
+            display_off,
+            clock_div,
+            multiplex,
+            display_offset,
+            start_line,
+            charge_pump,
+        ])?;
+        delay.delay_ms(CHARGE_PUMP_DELAY_MS);
+        self.send_commands([
+            segment_remap,
+            com_scan_direction,
+            com_pin_config,
+            contrast,
+            precharge_period,
+            vcomh_deselect,
+            test_screen,
+            image_mode,
+        ])?;
+        self.send_commands([display_on])?;
+        delay.delay_ms(DISPLAY_ON_DELAY_MS);
+
+        self.record_init();
+        Ok(())
+    }
+
+    /// Initializes the display with a custom command sequence.
+    ///
+    /// Usually a modified copy of `default_init`. The contrast, inversion and on/off state
+    /// reported by the getters follow the commands in the sequence. The segment remap and COM
+    /// scan direction are expected to match the current rotation. Like `init`, the sequence is
+    /// validated before anything is sent.
+    ///
+    /// # Arguments
+    ///
+    /// * `sequence` - The commands to send, from 1 to `MAX_COMMAND_BYTES` commands.
+    pub fn init_with<const M: usize>(
+        &mut self,
+        sequence: [Command; M],
+    ) -> Result<(), MiniOledError> {
+        CommandBuffer::from(sequence).validate()?;
+        self.send_commands(sequence)?;
+        self.record_init();
+        for command in sequence {
+            match command {
+                Command::Contrast(contrast) => self.contrast = contrast,
+                Command::PositiveImageMode => self.inverted = false,
+                Command::NegativeImageMode => self.inverted = true,
+                Command::TurnDisplayOn => self.display_on = true,
+                Command::TurnDisplayOff => self.display_on = false,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Initializes the display and blanks its RAM.
+    ///
+    /// The display RAM holds random data after power-up, which `init` alone would show until
+    /// the first flush. This clears the canvas and sends the whole buffer right after `init`.
+    pub fn init_and_clear(&mut self) -> Result<(), MiniOledError> {
+        self.init()?;
+        self.canvas.get_mut_buffer().fill(0);
+        self.flush_all()
+    }
+
+    /// Brings the display back to the state right after `init_and_clear`, without a reset pin.
+    ///
+    /// Any scroll of SSD1306 controllers is stopped, the rotation goes back to `Rotate0` and
+    /// the initialization sequence is sent again, which restores the contrast, image mode,
+    /// start line and offset configured with `Sh1106Builder`. The canvas is then cleared and
+    /// sent to the display.
+    pub fn reset_to_defaults(&mut self) -> Result<(), MiniOledError> {
+        // The SH1106 has no scroll commands.
+        if self.canvas.get_controller() == Controller::Ssd1306 {
+            self.send_commands([Command::DisableScroll])?;
+        }
+        self.canvas.set_rotation(DisplayRotation::Rotate0);
+        self.init_and_clear()
+    }
+}
+
+#[cfg(feature = "async")]
+impl<CI: AsyncCommunicationInterface, const N: usize, const W: u32, const H: u32, const O: u8>
+    Sh1106<CI, N, W, H, O>
+{
+    /// Asynchronously flushes the entire display buffer to the screen, refreshing all pixels.
+    pub async fn flush_all_async(&mut self) -> Result<(), MiniOledError> {
+        for (commands, page_buffer) in self.canvas.full_pages(CI::MAX_DATA_BYTES) {
+            self.communication_interface
+                .write_command(&commands)
+                .await?;
+            self.communication_interface
+                .write_data(&page_buffer)
+                .await?;
+        }
+        self.canvas.reset_dirty_area();
+
+        #[cfg(feature = "diff")]
+        self.canvas.commit_frame();
+        Ok(())
+    }
+
+    /// Asynchronously flushes only the modified parts of the display buffer to the screen.
+    ///
+    /// Returns the number of display data bytes sent, like `flush`.
+    pub async fn flush_async(&mut self) -> Result<usize, MiniOledError> {
+        self.canvas
+            .flush_to_async(&mut self.communication_interface)
+            .await
+    }
+
+    /// Asynchronously flushes the modified parts of the display buffer like `flush_detailed`.
+    pub async fn flush_detailed_async(&mut self) -> Result<FlushStats, MiniOledError> {
+        self.canvas
+            .flush_detailed_to_async(&mut self.communication_interface)
+            .await
+    }
+
+    /// Asynchronously initializes the display.
+    ///
+    /// Sends the same sequence as `init`.
+    pub async fn init_async(&mut self) -> Result<(), MiniOledError> {
+        let init_sequence = CommandBuffer::from(self.default_init());
+        init_sequence.validate()?;
+
+        self.communication_interface
+            .write_command(&init_sequence)
+            .await?;
+        self.record_init();
+        Ok(())
     }
 }
@@ -0,0 +1,268 @@
+//! # Text
+//!
+//! A minimal text writer with a built-in 5x7 ASCII font, for projects that don't use
+//! `embedded-graphics`. It is available with the `text` feature.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! // let mut screen = ...;
+//! screen.get_mut_canvas().draw_text(0, 0, "Hello", true);
+//!
+//! // Or format directly into the canvas
+//! use core::fmt::Write;
+//! write!(screen.text_cursor(0, 8), "Temp: {}C", 21).unwrap();
+//! screen.flush().unwrap();
+//! ```
+
+use core::fmt;
+
+use crate::screen::canvas::Canvas;
+
+/// Width of a glyph of `FONT_5X7` in pixels.
+pub const GLYPH_WIDTH: u32 = 5;
+/// Height of a character cell in pixels, the glyph and one blank row.
+pub const CHAR_HEIGHT: u32 = 8;
+/// Width of a character cell in pixels, the glyph and one blank column.
+pub const CHAR_WIDTH: u32 = GLYPH_WIDTH + 1;
+
+/// 5x7 font for the printable ASCII characters from `' '` to `'~'`.
+///
+/// Each glyph is stored column by column, the least significant bit is the top row.
+pub const FONT_5X7: [[u8; GLYPH_WIDTH as usize]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00], // ' '
+    [0x00, 0x00, 0x5F, 0x00, 0x00], // '!'
+    [0x00, 0x07, 0x00, 0x07, 0x00], // '"'
+    [0x14, 0x7F, 0x14, 0x7F, 0x14], // '#'
+    [0x24, 0x2A, 0x7F, 0x2A, 0x12], // '$'
+    [0x23, 0x13, 0x08, 0x64, 0x62], // '%'
+    [0x36, 0x49, 0x55, 0x22, 0x50], // '&'
+    [0x00, 0x05, 0x03, 0x00, 0x00], // '\''
+    [0x00, 0x1C, 0x22, 0x41, 0x00], // '('
+    [0x00, 0x41, 0x22, 0x1C, 0x00], // ')'
+    [0x14, 0x08, 0x3E, 0x08, 0x14], // '*'
+    [0x08, 0x08, 0x3E, 0x08, 0x08], // '+'
+    [0x00, 0x50, 0x30, 0x00, 0x00], // ','
+    [0x08, 0x08, 0x08, 0x08, 0x08], // '-'
+    [0x00, 0x60, 0x60, 0x00, 0x00], // '.'
+    [0x20, 0x10, 0x08, 0x04, 0x02], // '/'
+    [0x3E, 0x51, 0x49, 0x45, 0x3E], // '0'
+    [0x00, 0x42, 0x7F, 0x40, 0x00], // '1'
+    [0x42, 0x61, 0x51, 0x49, 0x46], // '2'
+    [0x21, 0x41, 0x45, 0x4B, 0x31], // '3'
+    [0x18, 0x14, 0x12, 0x7F, 0x10], // '4'
+    [0x27, 0x45, 0x45, 0x45, 0x39], // '5'
+    [0x3C, 0x4A, 0x49, 0x49, 0x30], // '6'
+    [0x01, 0x71, 0x09, 0x05, 0x03], // '7'
+    [0x36, 0x49, 0x49, 0x49, 0x36], // '8'
+    [0x06, 0x49, 0x49, 0x29, 0x1E], // '9'
+    [0x00, 0x36, 0x36, 0x00, 0x00], // ':'
+    [0x00, 0x56, 0x36, 0x00, 0x00], // ';'
+    [0x08, 0x14, 0x22, 0x41, 0x00], // '<'
+    [0x14, 0x14, 0x14, 0x14, 0x14], // '='
+    [0x00, 0x41, 0x22, 0x14, 0x08], // '>'
+    [0x02, 0x01, 0x51, 0x09, 0x06], // '?'
+    [0x32, 0x49, 0x79, 0x41, 0x3E], // '@'
+    [0x7E, 0x11, 0x11, 0x11, 0x7E], // 'A'
+    [0x7F, 0x49, 0x49, 0x49, 0x36], // 'B'
+    [0x3E, 0x41, 0x41, 0x41, 0x22], // 'C'
+    [0x7F, 0x41, 0x41, 0x22, 0x1C], // 'D'
+    [0x7F, 0x49, 0x49, 0x49, 0x41], // 'E'
+    [0x7F, 0x09, 0x09, 0x01, 0x01], // 'F'
+    [0x3E, 0x41, 0x41, 0x51, 0x32], // 'G'
+    [0x7F, 0x08, 0x08, 0x08, 0x7F], // 'H'
+    [0x00, 0x41, 0x7F, 0x41, 0x00], // 'I'
+    [0x20, 0x40, 0x41, 0x3F, 0x01], // 'J'
+    [0x7F, 0x08, 0x14, 0x22, 0x41], // 'K'
+    [0x7F, 0x40, 0x40, 0x40, 0x40], // 'L'
+    [0x7F, 0x02, 0x04, 0x02, 0x7F], // 'M'
+    [0x7F, 0x04, 0x08, 0x10, 0x7F], // 'N'
+    [0x3E, 0x41, 0x41, 0x41, 0x3E], // 'O'
+    [0x7F, 0x09, 0x09, 0x09, 0x06], // 'P'
+    [0x3E, 0x41, 0x51, 0x21, 0x5E], // 'Q'
+    [0x7F, 0x09, 0x19, 0x29, 0x46], // 'R'
+    [0x46, 0x49, 0x49, 0x49, 0x31], // 'S'
+    [0x01, 0x01, 0x7F, 0x01, 0x01], // 'T'
+    [0x3F, 0x40, 0x40, 0x40, 0x3F], // 'U'
+    [0x1F, 0x20, 0x40, 0x20, 0x1F], // 'V'
+    [0x7F, 0x20, 0x18, 0x20, 0x7F], // 'W'
+    [0x63, 0x14, 0x08, 0x14, 0x63], // 'X'
+    [0x03, 0x04, 0x78, 0x04, 0x03], // 'Y'
+    [0x61, 0x51, 0x49, 0x45, 0x43], // 'Z'
+    [0x00, 0x7F, 0x41, 0x41, 0x00], // '['
+    [0x02, 0x04, 0x08, 0x10, 0x20], // '\\'
+    [0x00, 0x41, 0x41, 0x7F, 0x00], // ']'
+    [0x04, 0x02, 0x01, 0x02, 0x04], // '^'
+    [0x40, 0x40, 0x40, 0x40, 0x40], // '_'
+    [0x00, 0x01, 0x02, 0x04, 0x00], // '`'
+    [0x20, 0x54, 0x54, 0x54, 0x78], // 'a'
+    [0x7F, 0x48, 0x44, 0x44, 0x38], // 'b'
+    [0x38, 0x44, 0x44, 0x44, 0x20], // 'c'
+    [0x38, 0x44, 0x44, 0x48, 0x7F], // 'd'
+    [0x38, 0x54, 0x54, 0x54, 0x18], // 'e'
+    [0x08, 0x7E, 0x09, 0x01, 0x02], // 'f'
+    [0x08, 0x14, 0x54, 0x54, 0x3C], // 'g'
+    [0x7F, 0x08, 0x04, 0x04, 0x78], // 'h'
+    [0x00, 0x44, 0x7D, 0x40, 0x00], // 'i'
+    [0x20, 0x40, 0x44, 0x3D, 0x00], // 'j'
+    [0x00, 0x7F, 0x10, 0x28, 0x44], // 'k'
+    [0x00, 0x41, 0x7F, 0x40, 0x00], // 'l'
+    [0x7C, 0x04, 0x18, 0x04, 0x78], // 'm'
+    [0x7C, 0x08, 0x04, 0x04, 0x78], // 'n'
+    [0x38, 0x44, 0x44, 0x44, 0x38], // 'o'
+    [0x7C, 0x14, 0x14, 0x14, 0x08], // 'p'
+    [0x08, 0x14, 0x14, 0x18, 0x7C], // 'q'
+    [0x7C, 0x08, 0x04, 0x04, 0x08], // 'r'
+    [0x48, 0x54, 0x54, 0x54, 0x20], // 's'
+    [0x04, 0x3F, 0x44, 0x40, 0x20], // 't'
+    [0x3C, 0x40, 0x40, 0x20, 0x7C], // 'u'
+    [0x1C, 0x20, 0x40, 0x20, 0x1C], // 'v'
+    [0x3C, 0x40, 0x30, 0x40, 0x3C], // 'w'
+    [0x44, 0x28, 0x10, 0x28, 0x44], // 'x'
+    [0x0C, 0x50, 0x50, 0x50, 0x3C], // 'y'
+    [0x44, 0x64, 0x54, 0x4C, 0x44], // 'z'
+    [0x00, 0x08, 0x36, 0x41, 0x00], // '{'
+    [0x00, 0x00, 0x7F, 0x00, 0x00], // '|'
+    [0x00, 0x41, 0x36, 0x08, 0x00], // '}'
+    [0x08, 0x04, 0x08, 0x10, 0x08], // '~'
+];
+
+impl<const N: usize, const W: u32, const H: u32, const O: u8> Canvas<N, W, H, O> {
+    /// Draws a string with the built-in 5x7 font.
+    ///
+    /// Every character takes a cell of `CHAR_WIDTH` x `CHAR_HEIGHT` pixels, including its
+    /// background, so new text cleanly replaces old text. `'\n'` starts a new line at `x` and
+    /// characters outside of printable ASCII are drawn as `'?'`.
+    /// Pixels outside of the display are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The X coordinate of the top left corner of the first character.
+    /// * `y` - The Y coordinate of the top left corner of the first character.
+    /// * `s` - The text to draw.
+    /// * `on` - `true` to draw lit text on a dark background, `false` for the opposite.
+    pub fn draw_text(&mut self, x: u32, y: u32, s: &str, on: bool) {
+        self.draw_text_from(x, (x, y), s, on);
+    }
+
+    /// Returns a `TextCursor` writing lit text at `(x, y)`, for use with `write!`.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The X coordinate of the top left corner of the first character.
+    /// * `y` - The Y coordinate of the top left corner of the first character.
+    pub fn text_cursor(&mut self, x: u32, y: u32) -> TextCursor<'_, N, W, H, O> {
+        TextCursor {
+            canvas: self,
+            line_start: x,
+            position: (x, y),
+            on: true,
+        }
+    }
+
+    /// Draws `s` starting at `cursor`, where new lines start at `line_start`, and returns the
+    /// cursor position after the last character.
+    fn draw_text_from(
+        &mut self,
+        line_start: u32,
+        cursor: (u32, u32),
+        s: &str,
+        on: bool,
+    ) -> (u32, u32) {
+        let (mut cursor_x, mut cursor_y) = cursor;
+        for character in s.chars() {
+            if character == '\n' {
+                cursor_x = line_start;
+                cursor_y = cursor_y.saturating_add(CHAR_HEIGHT);
+                continue;
+            }
+
+            let glyph = glyph(character);
+            for column in 0..CHAR_WIDTH {
+                let bits = glyph.get(column as usize).copied().unwrap_or(0);
+                for row in 0..CHAR_HEIGHT {
+                    self.set_pixel(
+                        cursor_x.saturating_add(column),
+                        cursor_y.saturating_add(row),
+                        (bits >> row) & 1 == on as u8,
+                    );
+                }
+            }
+            cursor_x = cursor_x.saturating_add(CHAR_WIDTH);
+        }
+
+        (cursor_x, cursor_y)
+    }
+}
+
+/// A text position on a canvas that implements `core::fmt::Write`.
+///
+/// Every write draws at the current position with the built-in font and advances it, so
+/// consecutive writes continue where the previous one stopped. `'\n'` moves to the start of
+/// the next line.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use core::fmt::Write;
+///
+/// let mut cursor = screen.get_mut_canvas().text_cursor(0, 0);
+/// writeln!(cursor, "Temp: {}C", 21).unwrap();
+/// write!(cursor, "Hum: {}%", 40).unwrap();
+/// ```
+pub struct TextCursor<'a, const N: usize, const W: u32, const H: u32, const O: u8> {
+    canvas: &'a mut Canvas<N, W, H, O>,
+    line_start: u32,
+    position: (u32, u32),
+    on: bool,
+}
+
+impl<const N: usize, const W: u32, const H: u32, const O: u8> TextCursor<'_, N, W, H, O> {
+    /// Sets whether the text is drawn lit on a dark background (`true`, the default) or the
+    /// opposite.
+    pub fn on(mut self, on: bool) -> Self {
+        self.on = on;
+        self
+    }
+
+    /// Returns the position `(x, y)` where the next character is drawn.
+    pub fn position(&self) -> (u32, u32) {
+        self.position
+    }
+}
+
+impl<const N: usize, const W: u32, const H: u32, const O: u8> fmt::Write
+    for TextCursor<'_, N, W, H, O>
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.position = self
+            .canvas
+            .draw_text_from(self.line_start, self.position, s, self.on);
+        Ok(())
+    }
+}
+
+/// Allows `ufmt::uwrite!`, which avoids the code size of `core::fmt`. Available with the
+/// `ufmt` feature.
+#[cfg(feature = "ufmt")]
+impl<const N: usize, const W: u32, const H: u32, const O: u8> ufmt_write::uWrite
+    for TextCursor<'_, N, W, H, O>
+{
+    type Error = core::convert::Infallible;
+
+    fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+        self.position = self
+            .canvas
+            .draw_text_from(self.line_start, self.position, s, self.on);
+        Ok(())
+    }
+}
+
+/// Returns the glyph of `character`, or the one of `'?'` if the font doesn't have it.
+fn glyph(character: char) -> &'static [u8; GLYPH_WIDTH as usize] {
+    let index = match character {
+        ' '..='~' => character as usize - ' ' as usize,
+        _ => '?' as usize - ' ' as usize,
+    };
+    &FONT_5X7[index]
+}
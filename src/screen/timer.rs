@@ -0,0 +1,67 @@
+//! # Timer
+//!
+//! This module contains the `FrameTimer`, which paces animation loops with an
+//! `embedded_hal::delay::DelayNs` provider.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use mini_oled::screen::timer::FrameTimer;
+//!
+//! // let mut delay = ...; // Your DelayNs provider
+//! let timer = FrameTimer::new(30);
+//!
+//! loop {
+//!     // Draw the next frame...
+//!     screen.flush_paced(&mut delay, &timer).unwrap();
+//! }
+//! ```
+
+use embedded_hal::delay::DelayNs;
+
+/// Fixed delay between the frames of an animation, derived from a target frame rate.
+///
+/// This crate has no clock, so the time spent drawing and flushing can't be measured. The
+/// timer waits the whole frame interval after every frame, which makes the actual frame rate
+/// somewhat lower than the target but keeps it steady.
+///
+/// # Example
+///
+/// ```rust
+/// use mini_oled::screen::timer::FrameTimer;
+///
+/// let timer = FrameTimer::new(50);
+/// assert_eq!(timer.frame_interval_us(), 20_000);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameTimer {
+    frame_interval_us: u32,
+}
+
+impl FrameTimer {
+    /// Creates a timer for the given frame rate.
+    ///
+    /// # Arguments
+    ///
+    /// * `fps` - The target frames per second. `0` is treated as `1`.
+    pub const fn new(fps: u32) -> Self {
+        let fps = if fps == 0 { 1 } else { fps };
+        FrameTimer {
+            frame_interval_us: 1_000_000 / fps,
+        }
+    }
+
+    /// Returns the time waited after every frame, in microseconds.
+    pub const fn frame_interval_us(&self) -> u32 {
+        self.frame_interval_us
+    }
+
+    /// Waits one frame interval.
+    ///
+    /// # Arguments
+    ///
+    /// * `delay` - The delay provider.
+    pub fn wait<D: DelayNs>(&self, delay: &mut D) {
+        delay.delay_us(self.frame_interval_us);
+    }
+}
@@ -0,0 +1,932 @@
+use crate::screen::{
+    canvas::Canvas,
+    properties::{DisplayProperties, DisplayRotation},
+};
+
+type TestCanvas = Canvas<1024, 128, 64, 2>;
+
+fn new_canvas(display_rotation: DisplayRotation) -> TestCanvas {
+    Canvas::new(DisplayProperties::new(display_rotation))
+}
+
+#[test]
+fn draw_hline_matches_set_pixel() {
+    for display_rotation in [
+        DisplayRotation::Rotate0,
+        DisplayRotation::Rotate90,
+        DisplayRotation::Rotate180,
+        DisplayRotation::Rotate270,
+    ] {
+        let mut fast = new_canvas(display_rotation);
+        let mut slow = new_canvas(display_rotation);
+
+        fast.draw_hline(3, 13, 200, true);
+        fast.draw_hline(10, 13, 5, false);
+        for x in 3..203 {
+            slow.set_pixel(x, 13, true);
+        }
+        for x in 10..15 {
+            slow.set_pixel(x, 13, false);
+        }
+
+        assert_eq!(fast.get_buffer(), slow.get_buffer());
+        assert_eq!(fast.get_dirty_area(), slow.get_dirty_area());
+    }
+}
+
+#[test]
+fn draw_vline_matches_set_pixel() {
+    for display_rotation in [
+        DisplayRotation::Rotate0,
+        DisplayRotation::Rotate90,
+        DisplayRotation::Rotate180,
+        DisplayRotation::Rotate270,
+    ] {
+        let mut fast = new_canvas(display_rotation);
+        let mut slow = new_canvas(display_rotation);
+
+        fast.draw_vline(13, 3, 200, true);
+        fast.draw_vline(13, 10, 5, false);
+        fast.draw_vline(20, 17, 1, true);
+        for y in 3..203 {
+            slow.set_pixel(13, y, true);
+        }
+        for y in 10..15 {
+            slow.set_pixel(13, y, false);
+        }
+        slow.set_pixel(20, 17, true);
+
+        assert_eq!(fast.get_buffer(), slow.get_buffer());
+        assert_eq!(fast.get_dirty_area(), slow.get_dirty_area());
+    }
+}
+
+#[cfg(feature = "embedded-graphics-core")]
+#[test]
+fn fill_solid_matches_draw_iter() {
+    use embedded_graphics_core::{
+        Pixel,
+        pixelcolor::BinaryColor,
+        prelude::{DrawTarget, Point, Size},
+        primitives::{PointsIter, Rectangle},
+    };
+
+    let areas = [
+        Rectangle::new(Point::new(0, 0), Size::new(128, 64)),
+        Rectangle::new(Point::new(5, 3), Size::new(40, 20)),
+        Rectangle::new(Point::new(-4, 9), Size::new(20, 3)),
+        Rectangle::new(Point::new(100, 50), Size::new(60, 60)),
+        Rectangle::new(Point::new(17, 17), Size::new(1, 1)),
+    ];
+
+    for display_rotation in [
+        DisplayRotation::Rotate0,
+        DisplayRotation::Rotate90,
+        DisplayRotation::Rotate180,
+        DisplayRotation::Rotate270,
+    ] {
+        let mut fast = new_canvas(display_rotation);
+        let mut slow = new_canvas(display_rotation);
+
+        for (i, area) in areas.iter().enumerate() {
+            let color = BinaryColor::from(i % 2 == 0);
+            fast.fill_solid(area, color).unwrap();
+            slow.draw_iter(area.points().map(|point| Pixel(point, color)))
+                .unwrap();
+        }
+
+        assert_eq!(fast.get_buffer(), slow.get_buffer());
+        assert_eq!(fast.get_dirty_area(), slow.get_dirty_area());
+    }
+}
+
+#[cfg(feature = "embedded-graphics-core")]
+#[test]
+fn fill_contiguous_matches_draw_iter() {
+    use embedded_graphics_core::{
+        Pixel,
+        pixelcolor::BinaryColor,
+        prelude::{DrawTarget, Point, Size},
+        primitives::{PointsIter, Rectangle},
+    };
+
+    let areas = [
+        Rectangle::new(Point::new(0, 0), Size::new(128, 64)),
+        Rectangle::new(Point::new(5, 3), Size::new(40, 20)),
+        Rectangle::new(Point::new(-4, -9), Size::new(20, 13)),
+        Rectangle::new(Point::new(100, 50), Size::new(60, 60)),
+        Rectangle::new(Point::new(17, 17), Size::new(1, 1)),
+    ];
+
+    for display_rotation in [
+        DisplayRotation::Rotate0,
+        DisplayRotation::Rotate90,
+        DisplayRotation::Rotate180,
+        DisplayRotation::Rotate270,
+    ] {
+        let mut fast = new_canvas(display_rotation);
+        let mut slow = new_canvas(display_rotation);
+
+        for area in areas.iter() {
+            let colors = (0..).map(|i: u32| BinaryColor::from(!i.is_multiple_of(3)));
+            fast.fill_contiguous(area, colors.clone()).unwrap();
+            slow.draw_iter(
+                area.points()
+                    .zip(colors)
+                    .map(|(point, color)| Pixel(point, color)),
+            )
+            .unwrap();
+        }
+
+        assert_eq!(fast.get_buffer(), slow.get_buffer());
+        assert_eq!(fast.get_dirty_area(), slow.get_dirty_area());
+    }
+}
+
+#[cfg(feature = "embedded-graphics-core")]
+#[test]
+fn clear_fills_whole_buffer() {
+    use embedded_graphics_core::{pixelcolor::BinaryColor, prelude::DrawTarget};
+
+    let mut canvas = new_canvas(DisplayRotation::Rotate0);
+
+    canvas.clear(BinaryColor::On).unwrap();
+    assert!(canvas.get_buffer().iter().all(|&byte| byte == 0xFF));
+    assert_eq!(canvas.get_dirty_area(), ((0, 0), (127, 63)));
+
+    canvas.reset_dirty_area();
+    canvas.clear(BinaryColor::Off).unwrap();
+    assert!(canvas.get_buffer().iter().all(|&byte| byte == 0x00));
+    assert_eq!(canvas.get_dirty_area(), ((0, 0), (127, 63)));
+}
+
+#[test]
+fn try_set_pixel_reports_out_of_bounds() {
+    use crate::error::MiniOledError;
+
+    let mut canvas = new_canvas(DisplayRotation::Rotate90);
+
+    assert!(canvas.try_set_pixel(63, 127, true).is_ok());
+    assert!(matches!(
+        canvas.try_set_pixel(64, 0, true),
+        Err(MiniOledError::PixelOutOfBounds { x: 64, y: 0 })
+    ));
+    assert!(matches!(
+        canvas.try_set_pixel(0, 128, true),
+        Err(MiniOledError::PixelOutOfBounds { x: 0, y: 128 })
+    ));
+}
+
+#[test]
+fn blit_packs_rows_msb_first() {
+    let mut canvas = new_canvas(DisplayRotation::Rotate0);
+
+    // 10x2 bitmap, first row has the leftmost and the rightmost pixel set.
+    canvas.blit(4, 6, 10, &[0b1000_0000, 0b0100_0000, 0xFF, 0xC0], true);
+
+    let buffer = canvas.get_buffer();
+    assert_eq!(buffer[4], 0b1100_0000);
+    assert!(buffer[5..13].iter().all(|&byte| byte == 0b1000_0000));
+    assert_eq!(buffer[13], 0b1100_0000);
+    assert_eq!(canvas.get_dirty_area(), ((4, 6), (13, 7)));
+
+    canvas.blit(4, 6, 10, &[0b1000_0000, 0b0100_0000, 0xFF, 0xC0], false);
+
+    let buffer = canvas.get_buffer();
+    assert_eq!(buffer[4], 0);
+    assert!(buffer[5..13].iter().all(|&byte| byte == 0b0100_0000));
+    assert_eq!(buffer[13], 0);
+}
+
+#[test]
+fn blit_matches_set_pixel() {
+    use crate::screen::properties::RotationMode;
+
+    // 13x11 bitmap with a different pattern on every row, placed off the page boundaries and
+    // clipped by the right and bottom edges of the rotated canvases.
+    let data: std::vec::Vec<u8> = (0..22u8).map(|i| i.wrapping_mul(0x5B) ^ 0xA6).collect();
+
+    for rotation_mode in [RotationMode::PerPixel, RotationMode::FlushTime] {
+        for display_rotation in [
+            DisplayRotation::Rotate0,
+            DisplayRotation::Rotate90,
+            DisplayRotation::Rotate180,
+            DisplayRotation::Rotate270,
+        ] {
+            for (x, y) in [(5, 3), (120, 58), (60, 123)] {
+                let mut fast = new_canvas(display_rotation);
+                let mut slow = new_canvas(display_rotation);
+                fast.set_rotation_mode(rotation_mode);
+                slow.set_rotation_mode(rotation_mode);
+                fast.reset_dirty_area();
+                slow.reset_dirty_area();
+                fast.get_mut_buffer().fill(0x3C);
+                slow.get_mut_buffer().fill(0x3C);
+
+                fast.blit(x, y, 13, &data, true);
+                for (row, row_data) in (0..).zip(data.chunks_exact(2)) {
+                    for column in 0..13 {
+                        let bit = row_data[column as usize >> 3] & (0x80 >> (column & 7)) != 0;
+                        slow.set_pixel(x + column, y + row, bit);
+                    }
+                }
+
+                let case = (rotation_mode, display_rotation, x, y);
+                assert_eq!(fast.get_buffer(), slow.get_buffer(), "{case:?}");
+                assert_eq!(fast.get_dirty_area(), slow.get_dirty_area(), "{case:?}");
+            }
+        }
+    }
+}
+
+#[test]
+fn mark_dirty_clamps_and_merges() {
+    let mut canvas = new_canvas(DisplayRotation::Rotate0);
+
+    canvas.mark_dirty((10, 20), (30, 40));
+    assert_eq!(canvas.get_dirty_area(), ((10, 20), (30, 40)));
+
+    canvas.mark_dirty((5, 30), (500, 500));
+    assert_eq!(canvas.get_dirty_area(), ((5, 20), (127, 63)));
+
+    canvas.reset_dirty_area();
+    canvas.mark_dirty((200, 0), (300, 10));
+    assert_eq!(canvas.get_dirty_area(), ((u32::MAX, u32::MAX), (0, 0)));
+}
+
+#[test]
+fn mark_dirty_in_rotated_canvas_uses_buffer_coordinates() {
+    use crate::command::Page;
+
+    let mut canvas = new_canvas(DisplayRotation::Rotate90);
+    canvas.mark_dirty((10, 20), (300, 40));
+    // Columns are logical rows in `Rotate90`, clamped to the 128 columns of the panel.
+    assert_eq!(canvas.get_dirty_area(), ((20, 10), (40, 127)));
+
+    canvas.reset_dirty_area();
+    canvas.fill_page(Page::Page1, 120, 200, 0xFF);
+    assert_eq!(canvas.get_dirty_area(), ((8, 120), (15, 127)));
+
+    // Still set up for `Rotate0`, so the columns are mirrored in software.
+    let mut canvas = new_canvas(DisplayRotation::Rotate0);
+    canvas.set_logical_rotation(DisplayRotation::Rotate90);
+    canvas.fill_page(Page::Page1, 120, 200, 0xFF);
+    assert_eq!(canvas.get_dirty_area(), ((8, 0), (15, 7)));
+    let (min, max) = canvas.get_dirty_area();
+    assert!(
+        canvas
+            .pixels()
+            .filter(|&(_, _, on)| on)
+            .all(|(x, y, _)| (min.0..=max.0).contains(&x) && (min.1..=max.1).contains(&y))
+    );
+}
+
+#[test]
+fn invert_region_flips_pixels() {
+    for display_rotation in [DisplayRotation::Rotate0, DisplayRotation::Rotate90] {
+        let mut fast = new_canvas(display_rotation);
+        let mut slow = new_canvas(display_rotation);
+
+        fast.set_pixel(6, 6, true);
+        slow.set_pixel(6, 6, true);
+        fast.invert_region(4, 5, 10, 20);
+        for x in 4..14 {
+            for y in 5..25 {
+                slow.set_pixel(x, y, !(x == 6 && y == 6));
+            }
+        }
+
+        assert_eq!(fast.get_buffer(), slow.get_buffer());
+        assert_eq!(fast.get_dirty_area(), slow.get_dirty_area());
+    }
+}
+
+#[test]
+fn scroll_matches_set_pixel() {
+    for display_rotation in [
+        DisplayRotation::Rotate0,
+        DisplayRotation::Rotate90,
+        DisplayRotation::Rotate180,
+        DisplayRotation::Rotate270,
+    ] {
+        let (width, height) = match display_rotation {
+            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => (128, 64),
+            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => (64, 128),
+        };
+        let pixel = |x: u32, y: u32| (x * 7 + y * 3).is_multiple_of(5);
+
+        for rows in [0, 3, 8, 13, 64, 200] {
+            for (up, fill) in [(true, false), (false, true)] {
+                let mut scrolled = new_canvas(display_rotation);
+                let mut expected = new_canvas(display_rotation);
+                for x in 0..width {
+                    for y in 0..height {
+                        scrolled.set_pixel(x, y, pixel(x, y));
+                        let source = match up {
+                            true => y.checked_add(rows).filter(|&y| y < height),
+                            false => y.checked_sub(rows),
+                        };
+                        expected.set_pixel(x, y, source.map_or(fill, |y| pixel(x, y)));
+                    }
+                }
+
+                match up {
+                    true => scrolled.scroll_up(rows, fill),
+                    false => scrolled.scroll_down(rows, fill),
+                }
+                assert_eq!(scrolled.get_buffer(), expected.get_buffer());
+            }
+        }
+    }
+}
+
+#[test]
+fn pixels_follow_rotation() {
+    use std::vec::Vec;
+
+    for display_rotation in [
+        DisplayRotation::Rotate0,
+        DisplayRotation::Rotate90,
+        DisplayRotation::Rotate180,
+        DisplayRotation::Rotate270,
+    ] {
+        let mut canvas = new_canvas(display_rotation);
+        canvas.set_pixel(3, 5, true);
+        canvas.set_pixel(60, 9, true);
+
+        let lit: Vec<(u32, u32)> = canvas
+            .pixels()
+            .filter(|&(_, _, on)| on)
+            .map(|(x, y, _)| (x, y))
+            .collect();
+        assert_eq!(lit, [(3, 5), (60, 9)]);
+        assert_eq!(canvas.pixels().count(), 128 * 64);
+    }
+}
+
+#[cfg(feature = "text")]
+#[test]
+fn draw_text_uses_character_cells() {
+    let mut canvas = new_canvas(DisplayRotation::Rotate0);
+    canvas.get_mut_buffer().fill(0xFF);
+    canvas.draw_text(0, 8, "I!\n-", true);
+
+    let page = &canvas.get_buffer()[128..128 + 12];
+    assert_eq!(
+        page,
+        [
+            0x00, 0x41, 0x7F, 0x41, 0x00, 0x00, 0x00, 0x00, 0x5F, 0x00, 0x00, 0x00
+        ]
+    );
+    assert_eq!(
+        canvas.get_buffer()[256..262],
+        [0x08, 0x08, 0x08, 0x08, 0x08, 0x00]
+    );
+    assert_eq!(canvas.get_buffer()[12 + 128], 0xFF);
+    assert_eq!(canvas.get_dirty_area(), ((0, 8), (11, 23)));
+
+    canvas.draw_text(0, 0, "\u{e9}", false);
+    assert_eq!(
+        canvas.get_buffer()[..6],
+        [!0x02, !0x01, !0x51, !0x09, !0x06, 0xFF]
+    );
+}
+
+#[cfg(feature = "text")]
+#[test]
+fn text_cursor_matches_draw_text() {
+    use core::fmt::Write;
+
+    let mut expected = new_canvas(DisplayRotation::Rotate0);
+    expected.draw_text(2, 3, "T: 21C\nok", true);
+
+    let mut canvas = new_canvas(DisplayRotation::Rotate0);
+    let mut cursor = canvas.text_cursor(2, 3);
+    write!(cursor, "T: {}", 21).unwrap();
+    writeln!(cursor, "C").unwrap();
+    write!(cursor, "ok").unwrap();
+    assert_eq!(cursor.position(), (2 + 2 * 6, 11));
+
+    assert_eq!(canvas.get_buffer(), expected.get_buffer());
+}
+
+#[test]
+fn to_pbm_writes_logical_rows() {
+    use crate::error::MiniOledError;
+
+    let mut canvas = new_canvas(DisplayRotation::Rotate90);
+    canvas.set_pixel(0, 0, true);
+    canvas.set_pixel(9, 1, true);
+
+    let mut out = [0u8; 1100];
+    let image = canvas.to_pbm(&mut out).unwrap();
+    assert_eq!(&image[..10], b"P4\n64 128\n");
+    assert_eq!(image.len(), 10 + 8 * 128);
+    assert_eq!(image[10..12], [0x80, 0x00]);
+    assert_eq!(image[18..20], [0x00, 0x40]);
+
+    assert_eq!(
+        canvas.to_pbm(&mut [0u8; 100]),
+        Err(MiniOledError::DataBufferSizeError)
+    );
+}
+
+#[test]
+fn logical_rotation_matches_panel_rotation() {
+    let draw = |canvas: &mut TestCanvas| {
+        canvas.set_pixel(3, 5, true);
+        canvas.draw_hline(2, 20, 30, true);
+        canvas.invert_region(10, 15, 9, 12);
+        canvas.scroll_up(3, false);
+    };
+
+    for display_rotation in [
+        DisplayRotation::Rotate0,
+        DisplayRotation::Rotate90,
+        DisplayRotation::Rotate180,
+        DisplayRotation::Rotate270,
+    ] {
+        let mut hardware = new_canvas(display_rotation);
+        let mut logical = new_canvas(DisplayRotation::Rotate0);
+        logical.set_logical_rotation(display_rotation);
+        draw(&mut hardware);
+        draw(&mut logical);
+
+        // The panel stays mirrored for `Rotate0`, so the buffer is mirrored in software
+        // where `display_rotation` differs from it.
+        let (segment_remap, reverse_com) = display_rotation.panel_mirroring();
+        for column in 0..128 {
+            for row in 0..64 {
+                let is_on = |canvas: &TestCanvas, column: u32, row: u32| {
+                    canvas.get_buffer()[((row >> 3) * 128 + column) as usize] & (1 << (row & 7))
+                        != 0
+                };
+                let mirrored_column = if segment_remap { column } else { 127 - column };
+                let mirrored_row = if reverse_com { row } else { 63 - row };
+                assert_eq!(
+                    is_on(&hardware, column, row),
+                    is_on(&logical, mirrored_column, mirrored_row)
+                );
+            }
+        }
+        assert_eq!(
+            hardware.pixels().collect::<std::vec::Vec<_>>(),
+            logical.pixels().collect::<std::vec::Vec<_>>()
+        );
+    }
+}
+
+#[cfg(feature = "embedded-graphics-core")]
+#[test]
+fn region_clips_and_translates() {
+    use embedded_graphics_core::{
+        Pixel,
+        pixelcolor::BinaryColor,
+        prelude::{DrawTarget, OriginDimensions, Point, Size},
+        primitives::{PointsIter, Rectangle},
+    };
+
+    let region_area = Rectangle::new(Point::new(20, 10), Size::new(30, 12));
+    let areas = [
+        Rectangle::new(Point::new(-5, -5), Size::new(10, 10)),
+        Rectangle::new(Point::new(25, 2), Size::new(20, 20)),
+        Rectangle::new(Point::new(3, 4), Size::new(1, 1)),
+    ];
+
+    for display_rotation in [DisplayRotation::Rotate0, DisplayRotation::Rotate90] {
+        let mut fast = new_canvas(display_rotation);
+        let mut slow = new_canvas(display_rotation);
+
+        let mut region = fast.region(region_area);
+        assert_eq!(region.size(), Size::new(30, 12));
+        for (i, area) in areas.iter().enumerate() {
+            let color = BinaryColor::from(i % 2 == 0);
+            let colors = (0..).map(|i: u32| BinaryColor::from(i.is_multiple_of(3)));
+            region.fill_solid(area, color).unwrap();
+            region.fill_contiguous(area, colors).unwrap();
+            region
+                .draw_iter(area.points().map(|point| Pixel(point, color)))
+                .unwrap();
+        }
+
+        for (i, area) in areas.iter().enumerate() {
+            let color = BinaryColor::from(i % 2 == 0);
+            let colors = (0..).map(|i: u32| BinaryColor::from(i.is_multiple_of(3)));
+            let translated = |point: Point| point + region_area.top_left;
+            let visible = |Pixel(point, _): &Pixel<BinaryColor>| region_area.contains(*point);
+            slow.draw_iter(
+                area.points()
+                    .map(|point| Pixel(translated(point), color))
+                    .filter(visible),
+            )
+            .unwrap();
+            slow.draw_iter(
+                area.points()
+                    .zip(colors)
+                    .map(|(point, color)| Pixel(translated(point), color))
+                    .filter(visible),
+            )
+            .unwrap();
+            slow.draw_iter(
+                area.points()
+                    .map(|point| Pixel(translated(point), color))
+                    .filter(visible),
+            )
+            .unwrap();
+        }
+
+        assert_eq!(fast.get_buffer(), slow.get_buffer());
+        assert_eq!(fast.get_dirty_area(), slow.get_dirty_area());
+    }
+}
+
+#[cfg(feature = "ufmt")]
+#[test]
+fn text_cursor_supports_ufmt() {
+    let mut expected = new_canvas(DisplayRotation::Rotate0);
+    expected.draw_text(0, 0, "v=42", true);
+
+    let mut canvas = new_canvas(DisplayRotation::Rotate0);
+    let mut cursor = canvas.text_cursor(0, 0);
+    ufmt::uwrite!(cursor, "v={}", 42u8).unwrap();
+
+    assert_eq!(canvas.get_buffer(), expected.get_buffer());
+}
+
+#[test]
+fn fill_page_writes_raw_bytes() {
+    use crate::command::Page;
+
+    let mut canvas = new_canvas(DisplayRotation::Rotate0);
+    canvas.fill_page(Page::Page1, 120, 200, 0b1000_0001);
+
+    let buffer = canvas.get_buffer();
+    assert_eq!(buffer[128 + 119], 0);
+    assert!(
+        buffer[128 + 120..256]
+            .iter()
+            .all(|&byte| byte == 0b1000_0001)
+    );
+    assert!(canvas.pixels().any(|pixel| pixel == (127, 15, true)));
+    assert_eq!(canvas.get_dirty_area(), ((120, 8), (127, 15)));
+
+    canvas.reset_dirty_area();
+    canvas.fill_page(Page::Page7, 5, 4, 0xFF);
+    assert_eq!(canvas.get_dirty_area(), ((u32::MAX, u32::MAX), (0, 0)));
+}
+
+#[cfg(feature = "embedded-graphics-core")]
+#[test]
+fn rotated_canvas_size_is_logical() {
+    use embedded_graphics_core::{
+        Pixel,
+        pixelcolor::BinaryColor,
+        prelude::{DrawTarget, OriginDimensions, Point, Size},
+    };
+
+    let mut canvas = new_canvas(DisplayRotation::Rotate90);
+    assert_eq!(canvas.size(), Size::new(64, 128));
+
+    canvas
+        .draw_iter([Pixel(Point::new(10, 100), BinaryColor::On)])
+        .unwrap();
+    assert!(canvas.pixels().any(|pixel| pixel == (10, 100, true)));
+}
+
+#[test]
+fn blit_gray_thresholds_and_dithers() {
+    use crate::screen::properties::DitherMode;
+
+    let mut canvas = new_canvas(DisplayRotation::Rotate0);
+    canvas.blit_gray(
+        126,
+        0,
+        3,
+        &[0x00, 0x7F, 0x80, 0xFF, 0x10, 0x90],
+        DitherMode::Threshold(0x80),
+    );
+    assert_eq!(canvas.get_buffer()[126..128], [0b10, 0b00]);
+    assert_eq!(canvas.get_dirty_area(), ((126, 0), (127, 1)));
+
+    // Mid gray lights half of every 4x4 tile, black none and white all of them.
+    let mut canvas = new_canvas(DisplayRotation::Rotate0);
+    let levels = [0x00, 0x80, 0xFF];
+    for (i, level) in levels.into_iter().enumerate() {
+        canvas.blit_gray(i as u32 * 4, 0, 4, &[level; 16], DitherMode::Bayer4x4);
+    }
+    let lit = |column: u32| {
+        canvas
+            .pixels()
+            .filter(|&(x, y, on)| on && y < 4 && x / 4 == column)
+            .count()
+    };
+    assert_eq!((lit(0), lit(1), lit(2)), (0, 8, 16));
+}
+
+#[test]
+fn standalone_canvas_constructors() {
+    let mut canvas = TestCanvas::with_rotation(DisplayRotation::Rotate90);
+    assert_eq!(canvas.get_rotated_size(), (64, 128));
+    canvas.set_pixel(0, 0, true);
+    assert_eq!(canvas.pixels().filter(|&(_, _, on)| on).count(), 1);
+
+    let canvas = TestCanvas::default();
+    assert_eq!(canvas.get_rotated_size(), (128, 64));
+    assert!(canvas.get_buffer().iter().all(|&byte| byte == 0));
+}
+
+#[test]
+fn flush_to_sends_dirty_pages_through_interface() {
+    use crate::{
+        command::{Command, Page},
+        interface::mock::MockInterface,
+    };
+
+    let mut canvas = new_canvas(DisplayRotation::Rotate0);
+    let mut interface = MockInterface::<8, 8>::new();
+    canvas.set_pixel(3, 9, true);
+    canvas.flush_to(&mut interface).unwrap();
+
+    assert_eq!(
+        interface.commands(),
+        [Command::PageAddress(Page::Page1), Command::ColumnAddress(5),]
+    );
+    assert_eq!(interface.data(), [0b10]);
+
+    interface.clear();
+    canvas.flush_to(&mut interface).unwrap();
+    assert!(interface.commands().is_empty());
+}
+
+#[cfg(feature = "embedded-graphics-core")]
+#[test]
+fn draw_iter_with_core_pixels_sets_buffer_bytes() {
+    use embedded_graphics_core::{
+        Pixel,
+        pixelcolor::BinaryColor,
+        prelude::{DrawTarget, Point},
+    };
+
+    let mut canvas = TestCanvas::default();
+    canvas
+        .draw_iter([
+            Pixel(Point::new(0, 0), BinaryColor::On),
+            Pixel(Point::new(0, 7), BinaryColor::On),
+            Pixel(Point::new(1, 8), BinaryColor::On),
+            Pixel(Point::new(127, 63), BinaryColor::On),
+            Pixel(Point::new(0, 7), BinaryColor::Off),
+            Pixel(Point::new(-1, 0), BinaryColor::On),
+            Pixel(Point::new(128, 0), BinaryColor::On),
+        ])
+        .unwrap();
+
+    let buffer = canvas.get_buffer();
+    assert_eq!(buffer[0], 0b0000_0001);
+    assert_eq!(buffer[128 + 1], 0b0000_0001);
+    assert_eq!(buffer[1023], 0b1000_0000);
+    assert_eq!(buffer.iter().filter(|&&byte| byte != 0).count(), 3);
+    assert_eq!(canvas.get_dirty_area(), ((0, 0), (127, 63)));
+}
+
+#[test]
+fn buffer_guard_marks_whole_buffer_dirty_on_drop() {
+    let mut canvas = new_canvas(DisplayRotation::Rotate0);
+
+    {
+        let mut buffer = canvas.buffer_guard();
+        buffer[129] = 0x0F;
+        assert_eq!(buffer.len(), 1024);
+    }
+
+    assert_eq!(canvas.get_buffer()[129], 0x0F);
+    assert_eq!(canvas.get_dirty_area(), ((0, 0), (127, 63)));
+    assert_eq!(canvas.dirty_pages(usize::MAX).count(), 8);
+}
+
+#[cfg(feature = "embedded-graphics-core")]
+#[test]
+fn image_raw_matches_draw_iter() {
+    use embedded_graphics::{
+        Pixel,
+        image::{Image, ImageRaw},
+        pixelcolor::BinaryColor,
+        prelude::{DrawTarget, Drawable, Point, Size},
+        primitives::{PointsIter, Rectangle},
+    };
+
+    // 16x16 1bpp image with a different pattern in every byte.
+    let data: [u8; 32] = core::array::from_fn(|i| (i as u8).wrapping_mul(37) ^ 0xA5);
+    let raw = ImageRaw::<BinaryColor>::new(&data, 16);
+    let image_pixels = Rectangle::new(Point::zero(), Size::new(16, 16))
+        .points()
+        .map(|Point { x, y }| {
+            let byte = data[(y * 2 + x / 8) as usize];
+            Pixel(
+                Point::new(x, y),
+                BinaryColor::from(byte & (0x80 >> (x % 8)) != 0),
+            )
+        });
+
+    for display_rotation in [
+        DisplayRotation::Rotate0,
+        DisplayRotation::Rotate90,
+        DisplayRotation::Rotate180,
+        DisplayRotation::Rotate270,
+    ] {
+        for position in [Point::new(8, 16), Point::new(3, 5), Point::new(120, 60)] {
+            let mut fast = new_canvas(display_rotation);
+            let mut slow = new_canvas(display_rotation);
+            fast.get_mut_buffer().fill(0x5A);
+            slow.get_mut_buffer().fill(0x5A);
+
+            Image::new(&raw, position).draw(&mut fast).unwrap();
+            slow.draw_iter(
+                image_pixels
+                    .clone()
+                    .map(|Pixel(point, color)| Pixel(point + position, color)),
+            )
+            .unwrap();
+
+            assert_eq!(fast.get_buffer(), slow.get_buffer());
+            assert_eq!(fast.get_dirty_area(), slow.get_dirty_area());
+        }
+    }
+}
+
+#[cfg(feature = "debug-flush")]
+#[test]
+fn debug_flush_sends_inverted_page_outlines_and_keeps_buffer() {
+    use crate::interface::mock::MockInterface;
+
+    let mut canvas = new_canvas(DisplayRotation::Rotate0);
+    let mut interface = MockInterface::<8, 8>::new();
+    canvas.set_debug_flush(true);
+    canvas.set_pixel(2, 1, true);
+    canvas.set_pixel(5, 3, true);
+    canvas.set_pixel(9, 12, true);
+    // The outline doesn't widen the runs, page 1 still only sends column 9.
+    assert_eq!(canvas.flush_to(&mut interface), Ok(5));
+
+    assert_eq!(interface.data(), [!0b0010, 0x81, 0x81, !0b1000, !0b1_0000]);
+    assert_eq!(canvas.get_buffer()[2..6], [0b0010, 0, 0, 0b1000]);
+    assert_eq!(canvas.get_buffer()[128 + 9], 0b1_0000);
+    assert!(!canvas.is_dirty());
+}
+
+#[test]
+fn page_bytes_and_pages_expose_page_layout() {
+    use crate::command::Page;
+
+    let mut canvas: Canvas<384, 64, 48, 32> = Canvas::with_rotation(DisplayRotation::Rotate0);
+    canvas.set_pixel(5, 47, true);
+
+    assert_eq!(canvas.page_bytes(Page::Page5).len(), 64);
+    assert_eq!(canvas.page_bytes(Page::Page5)[5], 0x80);
+    assert!(canvas.page_bytes(Page::Page6).is_empty());
+
+    let pages: std::vec::Vec<_> = canvas.pages().collect();
+    assert_eq!(pages.len(), 6);
+    assert_eq!(pages[5], (Page::Page5, canvas.page_bytes(Page::Page5)));
+}
+
+#[test]
+fn draw_bitmap_combines_with_canvas() {
+    use crate::screen::properties::BlitMode;
+
+    let bitmap = [0b1100_0000];
+    for (mode, expected) in [
+        (BlitMode::Replace, [true, true, false, false]),
+        (BlitMode::Or, [true, true, true, false]),
+        (BlitMode::And, [false, true, false, false]),
+        (BlitMode::Xor, [true, false, true, false]),
+    ] {
+        let mut canvas = new_canvas(DisplayRotation::Rotate90);
+        canvas.set_pixel(1, 5, true);
+        canvas.set_pixel(2, 5, true);
+        canvas.reset_dirty_area();
+        canvas.draw_bitmap(0, 5, 4, &bitmap, mode);
+
+        let lit: std::vec::Vec<_> = canvas.pixels().filter(|&(_, _, on)| on).collect();
+        for (x, &on) in expected.iter().enumerate() {
+            assert_eq!(lit.contains(&(x as u32, 5, true)), on, "{mode:?} at {x}");
+        }
+        assert!(canvas.is_dirty());
+    }
+}
+
+#[test]
+fn display_rotation_degrees_round_trip() {
+    for display_rotation in [
+        DisplayRotation::Rotate0,
+        DisplayRotation::Rotate90,
+        DisplayRotation::Rotate180,
+        DisplayRotation::Rotate270,
+    ] {
+        let degrees = display_rotation.to_degrees();
+        assert_eq!(
+            DisplayRotation::from_degrees(degrees),
+            Some(display_rotation)
+        );
+        assert_eq!(
+            DisplayRotation::from_degrees(degrees + 360),
+            Some(display_rotation)
+        );
+    }
+    assert_eq!(DisplayRotation::from_degrees(100), None);
+}
+
+#[test]
+fn fast_mul_matches_multiplication() {
+    use crate::screen::fast_mul;
+
+    for right in [0, 1, 64, 72, 128, 132] {
+        for value in [0, 1, 7, 1000] {
+            assert_eq!(fast_mul(value, right), value * right);
+        }
+    }
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "fast_mul overflowed u32")]
+fn fast_mul_panics_on_overflow_in_debug() {
+    crate::screen::fast_mul(u32::MAX / 2, 128);
+}
+
+/// Flushes `canvas` through a mock interface and returns the commands and data sent.
+fn flushed(canvas: &mut TestCanvas) -> (std::vec::Vec<crate::command::Command>, std::vec::Vec<u8>) {
+    let mut interface = crate::interface::mock::MockInterface::<32, 1024>::new();
+    canvas.flush_to(&mut interface).unwrap();
+    (interface.commands().to_vec(), interface.data().to_vec())
+}
+
+#[test]
+fn flush_time_rotation_sends_same_bytes_as_per_pixel() {
+    use crate::{command::Page, screen::properties::RotationMode};
+
+    for (panel_rotation, display_rotation) in [
+        (DisplayRotation::Rotate90, DisplayRotation::Rotate90),
+        (DisplayRotation::Rotate270, DisplayRotation::Rotate270),
+        // Mirrored in software, since the panel is still set up for another rotation.
+        (DisplayRotation::Rotate0, DisplayRotation::Rotate90),
+        (DisplayRotation::Rotate180, DisplayRotation::Rotate270),
+    ] {
+        let mut per_pixel = new_canvas(panel_rotation);
+        let mut flush_time = new_canvas(panel_rotation);
+        flush_time.set_rotation_mode(RotationMode::FlushTime);
+        assert_eq!(flush_time.get_rotation_mode(), RotationMode::FlushTime);
+
+        for canvas in [&mut per_pixel, &mut flush_time] {
+            canvas.set_logical_rotation(display_rotation);
+            canvas.reset_dirty_area();
+            canvas.set_pixel(3, 100, true);
+            canvas.draw_hline(2, 17, 40, true);
+            canvas.draw_vline(60, 5, 90, true);
+            canvas.invert_region(10, 10, 20, 30);
+        }
+        assert_eq!(flush_time.get_dirty_area(), per_pixel.get_dirty_area());
+        #[cfg(feature = "diff")]
+        {
+            let runs = |canvas: &TestCanvas| -> std::vec::Vec<_> {
+                canvas
+                    .changed_runs(usize::MAX)
+                    .map(|(commands, data)| (commands.as_slice().to_vec(), data.to_vec()))
+                    .collect()
+            };
+            assert_eq!(runs(&flush_time), runs(&per_pixel));
+        }
+        assert_eq!(flushed(&mut flush_time), flushed(&mut per_pixel));
+        assert!(flush_time.pixels().eq(per_pixel.pixels()));
+        assert!(flush_time.page_bytes(Page::Page0).is_empty());
+
+        for canvas in [&mut per_pixel, &mut flush_time] {
+            #[cfg(feature = "debug-flush")]
+            canvas.set_debug_flush(true);
+            canvas.scroll_up(5, true);
+            canvas.fill_page(Page::Page3, 4, 20, 0x5A);
+        }
+        assert_eq!(flushed(&mut flush_time), flushed(&mut per_pixel));
+        assert!(flush_time.pixels().eq(per_pixel.pixels()));
+
+        for canvas in [&mut per_pixel, &mut flush_time] {
+            canvas.draw_test_pattern(crate::screen::properties::TestPattern::Border);
+        }
+        assert_eq!(flushed(&mut flush_time), flushed(&mut per_pixel));
+    }
+}
+
+#[test]
+fn flush_time_rotation_keeps_per_pixel_layout_in_rotate0() {
+    use crate::screen::properties::RotationMode;
+
+    let mut canvas = new_canvas(DisplayRotation::Rotate0);
+    canvas.set_rotation_mode(RotationMode::FlushTime);
+    canvas.set_pixel(3, 9, true);
+    assert_eq!(canvas.get_buffer()[128 + 3], 0b10);
+
+    // The logical layout in `Rotate90`: 16 pages of 64 logical columns.
+    canvas.set_logical_rotation(DisplayRotation::Rotate90);
+    canvas.get_mut_buffer().fill(0);
+    canvas.set_pixel(3, 9, true);
+    assert_eq!(canvas.get_buffer()[64 + 3], 0b10);
+}
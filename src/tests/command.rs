@@ -0,0 +1,313 @@
+use crate::command::{Command, HorizontalScrollDirection, NFrames, Page};
+
+#[test]
+fn horizontal_scroll_setup_interval() {
+    let command = Command::HorizontalScrollSetup(
+        HorizontalScrollDirection::Left,
+        Page::Page0,
+        NFrames::F25,
+        Page::Page7,
+    );
+    let (bytes, len) = command.to_bytes();
+
+    assert_eq!(len, 7);
+    assert_eq!(bytes[..len], [0x27, 0x00, 0x00, 0b110, 0x07, 0x00, 0xFF]);
+}
+
+#[test]
+fn page_from_u8_masks_to_three_bits() {
+    for val in 0..=u8::MAX {
+        assert_eq!(Page::from(val) as u8, val & 0b111);
+    }
+}
+
+#[test]
+fn page_from_y_and_byte_offset() {
+    for y in 0..64 {
+        let page = Page::from_y(y);
+        assert_eq!(page as u32, y / 8);
+        assert_eq!(page.byte_offset(128), (y / 8 * 128) as usize);
+    }
+    assert_eq!(Page::from_y(64), Page::Page0);
+    assert_eq!(Page::Page7.byte_offset(64), 448);
+}
+
+#[test]
+fn concat_command_buffers() {
+    use crate::command::CommandBuffer;
+
+    let power_on: CommandBuffer<2> = [Command::EnableChargePump, Command::TurnDisplayOn].into();
+    let contrast: CommandBuffer<1> = Command::Contrast(0x20).into();
+    let sequence: CommandBuffer<3> = power_on.concat(contrast);
+
+    let mut buffer = [0u8; 8];
+    assert_eq!(
+        sequence.to_bytes(&mut buffer).unwrap()[1..],
+        [0xAD, 0x8B, 0xAF, 0x81, 0x20]
+    );
+}
+
+#[test]
+fn command_buffer_iter_and_len() {
+    use crate::command::CommandBuffer;
+
+    let commands: CommandBuffer<2> = [Command::TurnDisplayOff, Command::Contrast(0x10)].into();
+    assert_eq!(commands.len(), 2);
+    assert!(!commands.is_empty());
+    assert!(
+        commands
+            .iter()
+            .eq(&[Command::TurnDisplayOff, Command::Contrast(0x10)])
+    );
+    assert!(CommandBuffer::<0>::from([]).is_empty());
+}
+
+#[test]
+fn command_bytes_round_trip() {
+    use crate::command::{PumpVoltage, VcomhLevel};
+
+    let commands = [
+        Command::Contrast(0x7F),
+        Command::EnableTestScreen,
+        Command::DisableTestScreen,
+        Command::PositiveImageMode,
+        Command::NegativeImageMode,
+        Command::TurnDisplayOn,
+        Command::TurnDisplayOff,
+        Command::ColumnAddressLow(0xA),
+        Command::ColumnAddressHigh(0x7),
+        Command::PageAddress(Page::Page5),
+        Command::StartLine(42),
+        Command::EnableSegmentRemap,
+        Command::DisableSegmentRemap,
+        Command::Multiplex(63),
+        Command::EnableReverseComDir,
+        Command::DisableReverseComDir,
+        Command::DisplayOffset(12),
+        Command::AlternativeComPinConfig,
+        Command::SequentialComPinConfig,
+        Command::DisplayClockDiv(0x8, 0x1),
+        Command::PreChargePeriod(0x1, 0xF),
+        Command::VcomhDeselect(VcomhLevel::V083),
+        Command::Noop,
+        Command::ReadModifyWriteStart,
+        Command::ReadModifyWriteEnd,
+        Command::EnableChargePump,
+        Command::DisableChargePump,
+        Command::EnableSsd1306ChargePump,
+        Command::DisableSsd1306ChargePump,
+        Command::PumpVoltage(PumpVoltage::V64),
+        Command::PumpVoltage(PumpVoltage::V90),
+        Command::HorizontalScrollSetup(
+            HorizontalScrollDirection::Right,
+            Page::Page2,
+            NFrames::F128,
+            Page::Page6,
+        ),
+        Command::EnableScroll,
+        Command::DisableScroll,
+    ];
+
+    for command in commands {
+        let (bytes, len) = command.to_bytes();
+        assert_eq!(Command::from_bytes(&bytes[..len]).unwrap(), (command, len));
+    }
+}
+
+#[test]
+fn command_from_invalid_bytes() {
+    use crate::error::MiniOledError;
+
+    assert!(matches!(
+        Command::from_bytes(&[]),
+        Err(MiniOledError::CommandBufferSizeError)
+    ));
+    assert!(matches!(
+        Command::from_bytes(&[0x81]),
+        Err(MiniOledError::CommandBufferSizeError)
+    ));
+    assert!(matches!(
+        Command::from_bytes(&[0xFF]),
+        Err(MiniOledError::UnknownCommandError)
+    ));
+    assert!(matches!(
+        Command::from_bytes(&[0xAD, 0x00]),
+        Err(MiniOledError::UnknownCommandError)
+    ));
+}
+
+#[test]
+fn command_display_shows_name_and_bytes() {
+    use std::string::ToString;
+
+    assert_eq!(Command::Contrast(128).describe(), "Contrast");
+    assert_eq!(
+        Command::Contrast(128).to_string(),
+        "Contrast(128) -> [0x81, 0x80]"
+    );
+    assert_eq!(
+        Command::TurnDisplayOn.to_string(),
+        "TurnDisplayOn -> [0xAF]"
+    );
+    assert_eq!(
+        Command::PageAddress(Page::Page3).to_string(),
+        "PageAddress(Page3) -> [0xB3]"
+    );
+}
+
+#[test]
+fn command_buffer_rejects_out_of_range_arguments() {
+    use crate::{command::CommandBuffer, error::MiniOledError};
+
+    for command in [
+        Command::ColumnAddressLow(0x10),
+        Command::ColumnAddressHigh(0x10),
+        Command::ColumnAddress(132),
+        Command::StartLine(64),
+        Command::Multiplex(14),
+        Command::Multiplex(64),
+        Command::DisplayOffset(64),
+        Command::DisplayClockDiv(0x10, 0x0),
+        Command::PreChargePeriod(0x1, 0x10),
+    ] {
+        let mut buffer = [0u8; 4];
+        assert_eq!(
+            CommandBuffer::from(command).to_bytes(&mut buffer),
+            Err(MiniOledError::InvalidCommandArgument)
+        );
+    }
+
+    assert_eq!(Command::Multiplex(15).validate(), Ok(()));
+    assert_eq!(Command::PreChargePeriod(0xF, 0xF).validate(), Ok(()));
+}
+
+#[test]
+fn vertical_scroll_commands() {
+    let area = Command::VerticalScrollArea {
+        top_fixed_rows: 8,
+        scroll_rows: 56,
+    };
+    let (bytes, len) = area.to_bytes();
+    assert_eq!(bytes[..len], [0xA3, 8, 56]);
+    assert_eq!(Command::from_bytes(&bytes[..len]).unwrap(), (area, len));
+
+    let setup = Command::VerticalAndHorizontalScrollSetup {
+        direction: HorizontalScrollDirection::Left,
+        start_page: Page::Page1,
+        interval: NFrames::F2,
+        end_page: Page::Page6,
+        vertical_offset: 3,
+    };
+    let (bytes, len) = setup.to_bytes();
+    assert_eq!(bytes[..len], [0x2A, 0x00, 0x01, 0b111, 0x06, 0x03]);
+    assert_eq!(Command::from_bytes(&bytes[..len]).unwrap(), (setup, len));
+
+    assert!(
+        Command::VerticalScrollArea {
+            top_fixed_rows: 64,
+            scroll_rows: 0,
+        }
+        .validate()
+        .is_err()
+    );
+
+    // Parsing accepts exactly the arguments `validate` does.
+    let full_area = Command::from_bytes(&[0xA3, 63, 64]).unwrap().0;
+    assert_eq!(full_area.validate(), Ok(()));
+    for bytes in [[0xA3, 64, 0], [0xA3, 0, 65], [0xA3, 0, 0x7F]] {
+        assert_eq!(
+            Command::from_bytes(&bytes),
+            Err(crate::error::MiniOledError::UnknownCommandError)
+        );
+    }
+}
+
+#[test]
+fn vcomh_level_and_nframes_try_from_u8() {
+    use crate::{command::VcomhLevel, error::MiniOledError};
+
+    for frames in [
+        NFrames::F2,
+        NFrames::F3,
+        NFrames::F4,
+        NFrames::F5,
+        NFrames::F25,
+        NFrames::F64,
+        NFrames::F128,
+        NFrames::F256,
+    ] {
+        assert_eq!(NFrames::try_from(frames as u8), Ok(frames));
+    }
+    assert_eq!(
+        NFrames::try_from(0b1000),
+        Err(MiniOledError::InvalidCommandArgument)
+    );
+
+    for level in [
+        VcomhLevel::V065,
+        VcomhLevel::V077,
+        VcomhLevel::V083,
+        VcomhLevel::Auto,
+    ] {
+        assert_eq!(VcomhLevel::try_from(level as u8), Ok(level));
+    }
+    for bits in [0b000, 0b101, 0b1100] {
+        assert_eq!(
+            VcomhLevel::try_from(bits),
+            Err(MiniOledError::InvalidCommandArgument)
+        );
+    }
+}
+
+#[test]
+fn column_address_expands_to_low_and_high_nibbles() {
+    let (bytes, len) = Command::ColumnAddress(0x83).to_bytes();
+    assert_eq!(len, 2);
+    assert_eq!(bytes[..len], [0x03, 0x18]);
+    assert_eq!(
+        Command::from_bytes(&bytes[..len]).unwrap(),
+        (Command::ColumnAddressLow(0x3), 1)
+    );
+}
+
+#[test]
+fn read_modify_write_opcodes() {
+    assert_eq!(Command::ReadModifyWriteStart.to_bytes().0[0], 0xE0);
+    assert_eq!(Command::ReadModifyWriteEnd.to_bytes().0[0], 0xEE);
+    assert_eq!(Command::ReadModifyWriteEnd.get_byte_size(), 1);
+}
+
+#[cfg(feature = "heapless")]
+#[test]
+fn dyn_command_buffer_push_extend_and_capacity() {
+    use crate::{command::DynCommandBuffer, error::MiniOledError};
+
+    let mut commands: DynCommandBuffer<3> = DynCommandBuffer::new();
+    assert!(commands.is_empty());
+    commands.push(Command::TurnDisplayOff).unwrap();
+    commands
+        .extend([Command::Contrast(0x20), Command::TurnDisplayOn])
+        .unwrap();
+    assert_eq!(commands.len(), 3);
+    assert_eq!(
+        commands.push(Command::Noop),
+        Err(MiniOledError::CommandBufferSizeError)
+    );
+
+    let mut buffer = [0u8; 5];
+    assert_eq!(
+        commands.to_bytes(&mut buffer).unwrap()[1..],
+        [0xAE, 0x81, 0x20, 0xAF]
+    );
+    assert_eq!(
+        commands.to_bytes(&mut [0u8; 4]),
+        Err(MiniOledError::CommandBufferSizeError)
+    );
+
+    commands.clear();
+    commands.push(Command::StartLine(64)).unwrap();
+    assert_eq!(
+        commands.validate(),
+        Err(MiniOledError::InvalidCommandArgument)
+    );
+}
@@ -0,0 +1,55 @@
+use std::vec::Vec;
+
+use embedded_hal::{delay::DelayNs, digital};
+
+/// Output pin stub which records every state it is set to.
+#[derive(Default)]
+pub struct RecordingPin {
+    pub states: Vec<bool>,
+}
+
+impl digital::ErrorType for RecordingPin {
+    type Error = core::convert::Infallible;
+}
+
+impl digital::OutputPin for RecordingPin {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.states.push(false);
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.states.push(true);
+        Ok(())
+    }
+}
+
+/// Output pin stub whose state changes always fail.
+#[allow(unused)]
+pub struct FailingPin;
+
+impl digital::ErrorType for FailingPin {
+    type Error = digital::ErrorKind;
+}
+
+impl digital::OutputPin for FailingPin {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        Err(digital::ErrorKind::Other)
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        Err(digital::ErrorKind::Other)
+    }
+}
+
+/// Delay stub which records every delay in nanoseconds instead of waiting.
+#[derive(Default)]
+pub struct RecordingDelay {
+    pub delays_ns: Vec<u32>,
+}
+
+impl DelayNs for RecordingDelay {
+    fn delay_ns(&mut self, ns: u32) {
+        self.delays_ns.push(ns);
+    }
+}
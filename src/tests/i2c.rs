@@ -1,4 +1,8 @@
-use embedded_hal::i2c::{self, I2c, Operation, SevenBitAddress, TenBitAddress};
+use std::vec::Vec;
+
+use embedded_hal::i2c::{
+    self, I2c, NoAcknowledgeSource, Operation, SevenBitAddress, TenBitAddress,
+};
 
 /// I2C0 hardware peripheral which supports both 7-bit and 10-bit addressing.
 #[allow(unused)]
@@ -7,12 +11,14 @@ pub struct I2c0;
 #[allow(unused)]
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum Error {
+    NoAcknowledge,
     // ...
 }
 
 impl i2c::Error for Error {
     fn kind(&self) -> i2c::ErrorKind {
         match *self {
+            Error::NoAcknowledge => i2c::ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address),
             // ...
         }
     }
@@ -43,3 +49,73 @@ impl I2c<TenBitAddress> for I2c0 {
         Ok(())
     }
 }
+
+#[cfg(feature = "async")]
+impl embedded_hal_async::i2c::I2c<SevenBitAddress> for I2c0 {
+    async fn transaction(
+        &mut self,
+        _address: u8,
+        _operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        // ...
+        Ok(())
+    }
+}
+
+/// I2C stub which records the address and bytes of every write transaction, and answers
+/// every read with `status`.
+///
+/// Transactions to an address in `absent` are recorded and not acknowledged, as are the
+/// first `nacks` transactions.
+#[derive(Default)]
+pub struct RecordingI2c {
+    pub writes: Vec<(u16, Vec<u8>)>,
+    pub absent: Vec<u16>,
+    pub nacks: usize,
+    pub status: u8,
+}
+
+impl RecordingI2c {
+    fn record(&mut self, address: u16, operations: &mut [Operation<'_>]) -> Result<(), Error> {
+        let mut bytes = Vec::new();
+        for operation in operations {
+            match operation {
+                Operation::Write(write) => bytes.extend_from_slice(write),
+                Operation::Read(read) => read.fill(self.status),
+            }
+        }
+        self.writes.push((address, bytes));
+        if self.nacks > 0 {
+            self.nacks -= 1;
+            return Err(Error::NoAcknowledge);
+        }
+        match self.absent.contains(&address) {
+            true => Err(Error::NoAcknowledge),
+            false => Ok(()),
+        }
+    }
+}
+
+impl i2c::ErrorType for RecordingI2c {
+    type Error = Error;
+}
+
+impl I2c<SevenBitAddress> for RecordingI2c {
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.record(address.into(), operations)
+    }
+}
+
+impl I2c<TenBitAddress> for RecordingI2c {
+    fn transaction(
+        &mut self,
+        address: u16,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.record(address, operations)
+    }
+}
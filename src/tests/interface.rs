@@ -0,0 +1,276 @@
+use crate::{
+    command::{Command, CommandBuffer},
+    error::MiniOledError,
+    interface::{CommunicationInterface, i2c::I2cInterface},
+    tests::i2c::RecordingI2c,
+};
+
+#[test]
+fn i2c_control_bytes() {
+    let mut interface = I2cInterface::new(RecordingI2c::default(), 0x3C);
+    interface
+        .write_command(&CommandBuffer::from([
+            Command::TurnDisplayOn,
+            Command::Contrast(0x10),
+        ]))
+        .unwrap();
+    interface.write_data(&[1, 2, 3]).unwrap();
+
+    let i2c = interface.release();
+    assert_eq!(i2c.writes[0], (0x3C, [0x00, 0xAF, 0x81, 0x10].into()));
+    assert_eq!(i2c.writes[1], (0x3C, [0x40, 1, 2, 3].into()));
+}
+
+#[test]
+fn i2c_custom_control_bytes() {
+    let mut interface = I2cInterface::with_control_bytes(RecordingI2c::default(), 0x3D, 0x80, 0xC0);
+    interface
+        .write_command(&CommandBuffer::from(Command::TurnDisplayOff))
+        .unwrap();
+    interface.write_data(&[0xFF]).unwrap();
+
+    let i2c = interface.release();
+    assert_eq!(i2c.writes[0], (0x3D, [0x80, 0xAE].into()));
+    assert_eq!(i2c.writes[1], (0x3D, [0xC0, 0xFF].into()));
+}
+
+#[test]
+fn i2c_10bit_address() {
+    let mut interface = I2cInterface::new_10bit(RecordingI2c::default(), 0x2BC);
+    interface
+        .write_command(&CommandBuffer::from(Command::TurnDisplayOn))
+        .unwrap();
+    interface.write_data(&[0x55]).unwrap();
+
+    let i2c = interface.release();
+    assert_eq!(i2c.writes[0], (0x2BC, [0x00, 0xAF].into()));
+    assert_eq!(i2c.writes[1], (0x2BC, [0x40, 0x55].into()));
+}
+
+#[test]
+fn i2c_command_frame_limit() {
+    use crate::interface::i2c::COMMAND_FRAME_SIZE;
+
+    let mut commands = [Command::Contrast(0xFF); 15];
+    commands[14] = Command::Noop;
+    let mut interface = I2cInterface::new(RecordingI2c::default(), 0x3C);
+    interface
+        .write_command(&CommandBuffer::from(commands))
+        .unwrap();
+    assert_eq!(interface.release().writes[0].1.len(), COMMAND_FRAME_SIZE);
+
+    let mut interface = I2cInterface::new(RecordingI2c::default(), 0x3C);
+    assert_eq!(
+        interface.write_command(&CommandBuffer::from([Command::Contrast(0xFF); 15])),
+        Err(MiniOledError::CommandBufferSizeError)
+    );
+}
+
+#[test]
+fn i2c_shared_bus() {
+    use core::cell::RefCell;
+
+    use embedded_hal::i2c::I2c;
+    use embedded_hal_bus::i2c::RefCellDevice;
+
+    let i2c_bus = RefCell::new(RecordingI2c::default());
+    let mut interface = I2cInterface::new(RefCellDevice::new(&i2c_bus), 0x3C);
+    let mut sensor = RefCellDevice::new(&i2c_bus);
+
+    interface
+        .write_command(&CommandBuffer::from(Command::TurnDisplayOn))
+        .unwrap();
+    sensor.write(0x48, &[0x01]).unwrap();
+    interface.write_data(&[0xAA]).unwrap();
+
+    let i2c = i2c_bus.into_inner();
+    assert_eq!(i2c.writes[0], (0x3C, [0x00, 0xAF].into()));
+    assert_eq!(i2c.writes[1], (0x48, [0x01].into()));
+    assert_eq!(i2c.writes[2], (0x3C, [0x40, 0xAA].into()));
+}
+
+#[test]
+fn i2c_scan_and_set_address() {
+    use crate::interface::i2c::scan;
+
+    let mut i2c = RecordingI2c {
+        absent: [0x3C].into(),
+        ..Default::default()
+    };
+    assert_eq!(scan(&mut i2c, &[0x3C, 0x3D]), Some(0x3D));
+    assert_eq!(scan(&mut i2c, &[0x3C]), None);
+    assert_eq!(i2c.writes[0], (0x3C, [0x00, 0xE3].into()));
+    assert_eq!(i2c.writes[1], (0x3D, [0x00, 0xE3].into()));
+
+    let mut interface = I2cInterface::new(RecordingI2c::default(), 0x3C);
+    interface.set_address(0x3D);
+    interface
+        .write_command(&CommandBuffer::from(Command::TurnDisplayOn))
+        .unwrap();
+    assert_eq!(interface.release().writes[0], (0x3D, [0x00, 0xAF].into()));
+}
+
+#[test]
+fn i2c_retries_on_nack() {
+    use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+
+    let i2c = RecordingI2c {
+        nacks: 2,
+        ..Default::default()
+    };
+    let mut interface = I2cInterface::new(i2c, 0x3C).with_retries(2);
+    interface.write_data(&[0x55]).unwrap();
+    assert_eq!(interface.release().writes.len(), 3);
+
+    let i2c = RecordingI2c {
+        nacks: 2,
+        ..Default::default()
+    };
+    let mut interface = I2cInterface::new(i2c, 0x3C).with_retries(1);
+    assert_eq!(
+        interface.write_command(&CommandBuffer::from(Command::TurnDisplayOn)),
+        Err(MiniOledError::I2cError(ErrorKind::NoAcknowledge(
+            NoAcknowledgeSource::Address
+        )))
+    );
+    assert_eq!(interface.release().writes.len(), 2);
+
+    let i2c = RecordingI2c {
+        nacks: 2,
+        status: 0x40,
+        ..Default::default()
+    };
+    let mut interface = I2cInterface::new(i2c, 0x3C).with_retries(2);
+    assert_eq!(interface.read_status(), Ok(0x40));
+    assert_eq!(interface.release().writes.len(), 3);
+}
+
+#[test]
+fn mock_records_init_and_flush() {
+    use crate::{interface::mock::MockInterface, screen::sh1106::Sh1106};
+
+    let mut screen = Sh1106::new(MockInterface::<32, 1024>::new());
+    screen.init().unwrap();
+    screen.get_mut_canvas().set_pixel(3, 9, true);
+    screen.flush().unwrap();
+
+    let mock = screen.release();
+    assert_eq!(mock.commands()[0], Command::TurnDisplayOff);
+    assert_eq!(mock.commands()[14], Command::TurnDisplayOn);
+    assert_eq!(
+        mock.commands()[15..],
+        [
+            Command::PageAddress(crate::command::Page::Page1),
+            Command::ColumnAddress(5),
+        ]
+    );
+    assert_eq!(mock.data(), [0b10]);
+    assert_eq!(mock.data_writes(), 1);
+}
+
+#[cfg(feature = "spi")]
+#[test]
+fn spi_toggles_dc_pin() {
+    use crate::{
+        interface::spi::SpiInterface,
+        tests::{hal::RecordingPin, spi::RecordingSpi},
+    };
+
+    let mut interface = SpiInterface::new(RecordingSpi::default(), RecordingPin::default());
+    interface
+        .write_command(&CommandBuffer::from([
+            Command::TurnDisplayOn,
+            Command::Contrast(0x10),
+        ]))
+        .unwrap();
+    interface.write_data(&[1, 2, 3]).unwrap();
+
+    let (spi, dc) = interface.release();
+    assert_eq!(spi.writes, [[0xAF, 0x81, 0x10].as_slice(), &[1, 2, 3]]);
+    assert_eq!(dc.states, [false, true]);
+}
+
+#[cfg(feature = "spi")]
+#[test]
+fn spi_reports_bus_and_pin_errors() {
+    use embedded_hal::{digital, spi};
+
+    use crate::{
+        interface::spi::SpiInterface,
+        tests::{
+            hal::{FailingPin, RecordingPin},
+            spi::{FailingSpi, RecordingSpi},
+        },
+    };
+
+    let mut interface = SpiInterface::new(FailingSpi, RecordingPin::default());
+    assert_eq!(
+        interface.write_data(&[0xFF]),
+        Err(MiniOledError::SpiBusError(spi::ErrorKind::Overrun))
+    );
+
+    let mut interface = SpiInterface::new(RecordingSpi::default(), FailingPin);
+    assert_eq!(
+        interface.write_command(&CommandBuffer::from(Command::TurnDisplayOn)),
+        Err(MiniOledError::PinError(digital::ErrorKind::Other))
+    );
+}
+
+#[cfg(feature = "spi")]
+#[test]
+fn spi_3wire_sends_dc_bit_in_9bit_words() {
+    use crate::{interface::spi::SpiInterface, tests::spi::RecordingSpi};
+
+    let mut interface = SpiInterface::new_3wire(RecordingSpi::<u16>::default());
+    interface
+        .write_command(&CommandBuffer::from(Command::Contrast(0x10)))
+        .unwrap();
+    let data: [u8; 40] = core::array::from_fn(|i| i as u8);
+    interface.write_data(&data).unwrap();
+
+    let (spi, _) = interface.release();
+    assert_eq!(spi.writes.len(), 3);
+    assert_eq!(spi.writes[0], [0x081, 0x010]);
+    assert_eq!(spi.writes[1].len(), 32);
+    assert_eq!(
+        spi.writes[2],
+        [0x120, 0x121, 0x122, 0x123, 0x124, 0x125, 0x126, 0x127]
+    );
+}
+
+#[test]
+fn i2c_data_with_and_without_copy() {
+    let data: [u8; 128] = core::array::from_fn(|i| i as u8);
+    for data_copy in [false, true] {
+        let mut interface =
+            I2cInterface::new(RecordingI2c::default(), 0x3C).with_data_copy(data_copy);
+        interface.write_data(&data).unwrap();
+        assert_eq!(
+            interface.write_data(&[0; 129]),
+            Err(MiniOledError::DataBufferSizeError)
+        );
+
+        let i2c = interface.release();
+        assert_eq!(i2c.writes.len(), 1);
+        assert_eq!(i2c.writes[0].1[0], 0x40);
+        assert_eq!(i2c.writes[0].1[1..], data);
+    }
+}
+
+#[test]
+fn i2c_splits_data_into_max_transfer_bytes() {
+    let data: [u8; 100] = core::array::from_fn(|i| i as u8);
+    let mut interface =
+        I2cInterface::new(RecordingI2c::default(), 0x3C).with_max_transfer_bytes(32);
+    interface.write_data(&data).unwrap();
+
+    let i2c = interface.release();
+    assert_eq!(
+        i2c.writes
+            .iter()
+            .map(|(_, bytes)| bytes.len())
+            .collect::<std::vec::Vec<_>>(),
+        [33, 33, 33, 5]
+    );
+    assert_eq!(i2c.writes[3].1, [0x40, 96, 97, 98, 99]);
+}
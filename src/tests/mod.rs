@@ -1,2 +1,9 @@
+mod canvas;
+mod command;
+mod hal;
 mod i2c;
+mod interface;
 mod sh1106;
+mod snapshot;
+#[cfg(feature = "spi")]
+mod spi;
@@ -14,3 +14,1144 @@ fn create_sh1106() {
         .set_rotation(screen::properties::DisplayRotation::Rotate0)
         .unwrap();
 }
+
+#[test]
+fn build_sh1106() {
+    let i2c = I2cInterface::new(I2c0, 0x3C);
+    let mut screen = screen::builder::Sh1106Builder::new()
+        .contrast(0x40)
+        .com_pin_config(screen::properties::ComPinConfig::Sequential)
+        .precharge_period(0x2, 0x2)
+        .display_clock_div(0xF, 0x0)
+        .charge_pump(false)
+        .build(i2c);
+
+    screen.init().unwrap();
+}
+
+#[test]
+fn release_sh1106() {
+    let i2c = I2cInterface::new(I2c0, 0x3C);
+    let screen = screen::sh1106::Sh1106::new(i2c);
+    let _i2c: I2cInterface<I2c0> = screen.release();
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn flush_sh1106_async() {
+    use core::{
+        future::Future,
+        pin::pin,
+        task::{Context, Poll, Waker},
+    };
+
+    let i2c = I2cInterface::new(I2c0, 0x3C);
+    let mut screen = screen::sh1106::Sh1106::new(i2c);
+
+    let mut future = pin!(async {
+        screen.init_async().await?;
+        screen.get_mut_canvas().set_pixel(10, 10, true);
+        screen.flush_async().await
+    });
+    let mut context = Context::from_waker(Waker::noop());
+
+    assert!(matches!(
+        future.as_mut().poll(&mut context),
+        Poll::Ready(Ok(1))
+    ));
+}
+
+#[test]
+fn flush_sends_only_dirty_columns_of_each_page() {
+    use crate::{command::Command, command::Page, interface::mock::MockInterface};
+
+    let mut screen = screen::sh1106::Sh1106::new(MockInterface::<16, 1024>::new());
+    let canvas = screen.get_mut_canvas();
+    canvas.set_pixel(0, 0, true);
+    canvas.set_pixel(100, 20, true);
+    screen.flush().unwrap();
+
+    let mock = screen.release();
+    assert_eq!(
+        mock.commands(),
+        [
+            Command::PageAddress(Page::Page0),
+            Command::ColumnAddress(2),
+            Command::PageAddress(Page::Page2),
+            Command::ColumnAddress(102),
+        ]
+    );
+    assert_eq!(mock.data(), [0b1, 0b1_0000]);
+}
+
+#[test]
+fn start_line_and_display_offset_are_validated() {
+    use crate::{command::Command, error::MiniOledError, interface::mock::MockInterface};
+
+    let mut screen = screen::sh1106::Sh1106::new(MockInterface::<4, 0>::new());
+    screen.set_start_line(63).unwrap();
+    screen.set_display_offset(7).unwrap();
+    assert!(matches!(
+        screen.set_start_line(64),
+        Err(MiniOledError::InvalidCommandArgument)
+    ));
+    assert!(matches!(
+        screen.set_display_offset(64),
+        Err(MiniOledError::InvalidCommandArgument)
+    ));
+
+    let mock = screen.release();
+    assert_eq!(
+        mock.commands(),
+        [Command::StartLine(63), Command::DisplayOffset(7)]
+    );
+}
+
+#[test]
+fn fade_ramps_contrast() {
+    use crate::{command::Command, error::MiniOledError, interface::mock::MockInterface};
+
+    let mut screen = screen::sh1106::Sh1106::new(MockInterface::<8, 0>::new());
+    screen.fade(0x00, 0x25, 0x10).unwrap();
+    screen.fade(0x20, 0x10, 0x20).unwrap();
+    assert_eq!(
+        screen.fade(0x00, 0x10, 0),
+        Err(MiniOledError::InvalidCommandArgument)
+    );
+
+    let mock = screen.release();
+    assert_eq!(
+        mock.commands(),
+        [
+            Command::Contrast(0x00),
+            Command::Contrast(0x10),
+            Command::Contrast(0x20),
+            Command::Contrast(0x25),
+            Command::Contrast(0x20),
+            Command::Contrast(0x10),
+        ]
+    );
+}
+
+#[test]
+fn init_and_clear_blanks_the_display() {
+    use crate::interface::mock::MockInterface;
+
+    let mut screen = screen::sh1106::Sh1106::new(MockInterface::<64, 1024>::new());
+    screen.get_mut_canvas().get_mut_buffer().fill(0xAA);
+    screen.init_and_clear().unwrap();
+
+    let mock = screen.release();
+    assert_eq!(mock.data().len(), 1024);
+    assert!(mock.data().iter().all(|&byte| byte == 0));
+}
+
+#[test]
+fn sh1106_64x48_uses_offset_and_multiplex() {
+    use crate::{command::Command, command::Page, interface::mock::MockInterface};
+
+    let mut screen = screen::sh1106::Sh1106::new_64x48(MockInterface::<64, 1024>::new());
+    screen.init().unwrap();
+    screen.flush_all().unwrap();
+
+    let mock = screen.release();
+    assert_eq!(mock.commands()[2], Command::Multiplex(47));
+    assert_eq!(
+        mock.commands()[15..17],
+        [
+            Command::PageAddress(Page::Page0),
+            Command::ColumnAddress(32),
+        ]
+    );
+    assert_eq!(mock.commands().len(), 15 + 6 * 2);
+    assert_eq!(mock.data().len(), 64 * 48 / 8);
+}
+
+#[test]
+fn draw_test_pattern_fills_ram() {
+    use crate::{interface::mock::MockInterface, screen::properties::TestPattern};
+
+    let mut screen = screen::sh1106::Sh1106::new(MockInterface::<64, 1024>::new());
+
+    screen.draw_test_pattern(TestPattern::Checkerboard).unwrap();
+    let mock = screen.release();
+    let data = mock.data();
+    assert_eq!(
+        data[0..16],
+        [
+            0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0, 0, 0, 0, 0, 0, 0, 0
+        ]
+    );
+    assert_eq!(data[128..136], [0; 8]);
+    assert_eq!(data[136..144], [0xFF; 8]);
+
+    let mut screen = screen::sh1106::Sh1106::new(MockInterface::<64, 1024>::new());
+    screen.draw_test_pattern(TestPattern::Border).unwrap();
+    let mock = screen.release();
+    let data = mock.data();
+    assert_eq!(data[0..3], [0xFF, 0x01, 0x01]);
+    assert_eq!(data[127], 0xFF);
+    assert_eq!(data[128..131], [0xFF, 0x00, 0x00]);
+    assert_eq!(data[7 * 128..7 * 128 + 3], [0xFF, 0x80, 0x80]);
+}
+
+#[test]
+fn sh1106_with_custom_size() {
+    use crate::{command::Command, interface::mock::MockInterface};
+
+    let mut screen: screen::sh1106::Sh1106<_, 512, 128, 32, 2> =
+        screen::sh1106::Sh1106::new_with_size(MockInterface::<64, 1024>::new());
+    screen.init().unwrap();
+    screen.flush_all().unwrap();
+
+    let mock = screen.release();
+    assert_eq!(mock.commands()[2], Command::Multiplex(31));
+    assert_eq!(mock.commands().len(), 15 + 4 * 2);
+    assert_eq!(mock.data().len(), 128 * 32 / 8);
+}
+
+#[test]
+fn build_with_size_applies_init_config() {
+    use crate::{command::Command, interface::mock::MockInterface};
+    use screen::properties::ComPinConfig;
+
+    let mut screen: screen::sh1106::Sh1106<_, 384, 64, 48, 32> =
+        screen::builder::Sh1106Builder::new()
+            .com_pin_config(ComPinConfig::Sequential)
+            .precharge_period(0x2, 0x2)
+            .build_with_size(MockInterface::<64, 0>::new());
+    screen.init().unwrap();
+
+    let mock = screen.release();
+    assert_eq!(mock.commands()[2], Command::Multiplex(47));
+    assert_eq!(mock.commands()[8], Command::SequentialComPinConfig);
+    assert_eq!(mock.commands()[10], Command::PreChargePeriod(0x2, 0x2));
+}
+
+#[test]
+fn flush_region_keeps_dirty_area() {
+    use crate::{command::Command, command::Page, interface::mock::MockInterface};
+
+    let mut screen = screen::sh1106::Sh1106::new(MockInterface::<64, 1024>::new());
+    screen.get_mut_canvas().set_pixel(100, 60, true);
+    screen.flush_region(10, 5, 4, 6).unwrap();
+    assert_eq!(screen.get_canvas().get_dirty_area(), ((100, 60), (100, 60)));
+
+    screen.flush().unwrap();
+    let mock = screen.release();
+    assert_eq!(
+        mock.commands(),
+        [
+            Command::PageAddress(Page::Page0),
+            Command::ColumnAddress(12),
+            Command::PageAddress(Page::Page1),
+            Command::ColumnAddress(12),
+            Command::PageAddress(Page::Page7),
+            Command::ColumnAddress(102),
+        ]
+    );
+    assert_eq!(mock.data_writes(), 3);
+    assert_eq!(mock.data().len(), 4 + 4 + 1);
+}
+
+#[test]
+fn ssd1306_controller_uses_its_offset_and_charge_pump() {
+    use crate::{
+        command::Command, command::Page, interface::mock::MockInterface,
+        screen::properties::Controller,
+    };
+
+    let mut screen = screen::builder::Sh1106Builder::new()
+        .controller(Controller::Ssd1306)
+        .build(MockInterface::<64, 1024>::new());
+    screen.init().unwrap();
+    screen.flush_all().unwrap();
+
+    let mock = screen.release();
+    assert_eq!(mock.commands()[5], Command::EnableSsd1306ChargePump);
+    assert_eq!(
+        mock.commands()[15..17],
+        [Command::PageAddress(Page::Page0), Command::ColumnAddress(0),]
+    );
+}
+
+#[test]
+fn ssd1306_64x48_uses_explicit_column_offset() {
+    use crate::{
+        command::Command, command::Page, interface::mock::MockInterface,
+        screen::properties::Controller,
+    };
+
+    // Derived from the SH1106 offset, which is wrong for these panels.
+    let mut screen: screen::sh1106::Sh1106<_, 384, 64, 48, 32> =
+        screen::builder::Sh1106Builder::new()
+            .controller(Controller::Ssd1306)
+            .build_with_size(MockInterface::<2, 64>::new());
+    screen.get_mut_canvas().set_pixel(0, 0, true);
+    screen.flush().unwrap();
+    assert_eq!(
+        screen.release().commands(),
+        [
+            Command::PageAddress(Page::Page0),
+            Command::ColumnAddress(30)
+        ]
+    );
+
+    let mut screen: screen::sh1106::Sh1106<_, 384, 64, 48, 32> =
+        screen::builder::Sh1106Builder::new()
+            .controller(Controller::Ssd1306)
+            .column_offset(32)
+            .build_with_size(MockInterface::<32, 64>::new());
+    screen.init().unwrap();
+    screen.get_mut_canvas().set_pixel(0, 0, true);
+    screen.flush().unwrap();
+
+    let mock = screen.release();
+    assert_eq!(mock.commands()[5], Command::EnableSsd1306ChargePump);
+    assert_eq!(
+        mock.commands()[15..],
+        [
+            Command::PageAddress(Page::Page0),
+            Command::ColumnAddress(32)
+        ]
+    );
+}
+
+#[test]
+fn column_offset_past_controller_ram_fails_flush() {
+    use crate::{error::MiniOledError, interface::mock::MockInterface};
+
+    for column_offset in [5, 200] {
+        let mut screen: screen::sh1106::Sh1106<_, 1024, 128, 64, 2> =
+            screen::builder::Sh1106Builder::new()
+                .column_offset(column_offset)
+                .build_with_size(MockInterface::<2, 128>::new());
+        screen.get_mut_canvas().set_pixel(127, 0, true);
+        assert_eq!(
+            screen.flush(),
+            Err(MiniOledError::InvalidCommandArgument),
+            "{column_offset}"
+        );
+    }
+}
+
+#[test]
+fn dirty_bounds_tracks_changes() {
+    let mut screen = screen::sh1106::Sh1106::new(I2cInterface::new(I2c0, 0x3C));
+    assert_eq!(screen.dirty_bounds(), None);
+
+    screen.get_mut_canvas().set_pixel(0, 0, true);
+    screen.get_mut_canvas().set_pixel(20, 9, true);
+    assert_eq!(screen.dirty_bounds(), Some(((0, 0), (20, 9))));
+
+    screen.flush().unwrap();
+    assert_eq!(screen.dirty_bounds(), None);
+}
+
+#[test]
+fn reset_toggles_pin() {
+    use crate::tests::hal::{RecordingDelay, RecordingPin};
+
+    let mut screen = screen::sh1106::Sh1106::new(I2cInterface::new(I2c0, 0x3C));
+    let mut reset_pin = RecordingPin::default();
+    let mut delay = RecordingDelay::default();
+    screen.reset(&mut reset_pin, &mut delay).unwrap();
+
+    assert_eq!(reset_pin.states, [true, false, true]);
+    assert_eq!(delay.delays_ns[1], 10_000);
+}
+
+#[test]
+fn init_sequence_fits_i2c_command_frame() {
+    use crate::{
+        interface::i2c::COMMAND_FRAME_SIZE, screen::properties::Controller,
+        tests::i2c::RecordingI2c,
+    };
+
+    for controller in [Controller::Sh1106, Controller::Ssd1306] {
+        let mut screen = screen::builder::Sh1106Builder::new()
+            .controller(controller)
+            .build(I2cInterface::new(RecordingI2c::default(), 0x3C));
+        screen.init().unwrap();
+
+        let i2c = screen.release().release();
+        assert_eq!(i2c.writes[0].1.len(), 24);
+        assert!(i2c.writes[0].1.len() <= COMMAND_FRAME_SIZE);
+    }
+}
+
+#[test]
+fn set_vcomh_pump_voltage_and_precharge() {
+    use crate::{
+        command::{Command, PumpVoltage, VcomhLevel},
+        error::MiniOledError,
+        interface::mock::MockInterface,
+    };
+
+    let mut screen = screen::sh1106::Sh1106::new(MockInterface::<8, 8>::new());
+    screen.set_vcomh(VcomhLevel::V077).unwrap();
+    screen.set_pump_voltage(PumpVoltage::V90).unwrap();
+    screen.set_precharge(0x2, 0xF).unwrap();
+    assert_eq!(
+        screen.set_precharge(0x10, 0x2),
+        Err(MiniOledError::InvalidCommandArgument)
+    );
+
+    let mock = screen.release();
+    assert_eq!(
+        mock.commands(),
+        [
+            Command::VcomhDeselect(VcomhLevel::V077),
+            Command::PumpVoltage(PumpVoltage::V90),
+            Command::PreChargePeriod(0x2, 0xF),
+        ]
+    );
+
+    // The SSD1306 has no pump voltage command.
+    let mut screen = screen::builder::Sh1106Builder::new()
+        .controller(screen::properties::Controller::Ssd1306)
+        .build(MockInterface::<8, 8>::new());
+    assert_eq!(
+        screen.set_pump_voltage(PumpVoltage::V90),
+        Err(MiniOledError::UnsupportedOperation)
+    );
+    assert!(screen.release().commands().is_empty());
+}
+
+#[test]
+fn send_commands_writes_one_buffer() {
+    use crate::{
+        command::Command, error::MiniOledError, interface::i2c::COMMAND_FRAME_SIZE,
+        tests::i2c::RecordingI2c,
+    };
+
+    let mut screen = screen::sh1106::Sh1106::new(I2cInterface::new(RecordingI2c::default(), 0x3C));
+    screen
+        .send_commands([Command::NegativeImageMode, Command::Contrast(0x20)])
+        .unwrap();
+    assert_eq!(
+        screen.send_commands([Command::Contrast(0x20); COMMAND_FRAME_SIZE / 2]),
+        Err(MiniOledError::CommandBufferSizeError)
+    );
+
+    let i2c = screen.release().release();
+    assert_eq!(i2c.writes, [(0x3C, [0x00, 0xA7, 0x81, 0x20].into())]);
+}
+
+#[cfg(feature = "diff")]
+#[test]
+fn flush_diff_sends_changed_runs() {
+    use crate::{command::Command, command::Page, interface::mock::MockInterface};
+
+    let mut screen = screen::sh1106::Sh1106::new(MockInterface::<64, 1024>::new());
+    screen.flush_all().unwrap();
+    let mut mock = screen.release();
+    mock.clear();
+
+    let mut screen = screen::sh1106::Sh1106::new(mock);
+    screen.flush_diff().unwrap();
+    screen.get_mut_canvas().set_pixel(0, 0, true);
+    screen.get_mut_canvas().set_pixel(3, 0, true);
+    screen.get_mut_canvas().set_pixel(127, 63, true);
+    screen.flush_diff().unwrap();
+    screen.flush_diff().unwrap();
+
+    let mock = screen.release();
+    assert_eq!(
+        mock.commands(),
+        [
+            Command::PageAddress(Page::Page0),
+            Command::ColumnAddress(2),
+            Command::PageAddress(Page::Page7),
+            Command::ColumnAddress(129),
+        ]
+    );
+    assert_eq!(mock.data(), [0x01, 0x00, 0x00, 0x01, 0x80]);
+}
+
+#[test]
+fn display_state_getters() {
+    use crate::interface::mock::MockInterface;
+
+    let mut screen = screen::builder::Sh1106Builder::new()
+        .contrast(0x40)
+        .build(MockInterface::<64, 8>::new());
+    assert!(!screen.is_display_on());
+
+    screen.init().unwrap();
+    assert_eq!(screen.get_contrast(), 0x40);
+    assert!(screen.is_display_on());
+    assert!(!screen.is_inverted());
+
+    screen.set_inverted(true).unwrap();
+    screen.set_display_on(false).unwrap();
+    screen.fade(0x40, 0x10, 0x20).unwrap();
+    assert!(screen.is_inverted());
+    assert!(!screen.is_display_on());
+    assert_eq!(screen.get_contrast(), 0x10);
+}
+
+#[test]
+fn set_logical_rotation_sends_no_commands() {
+    use crate::interface::mock::MockInterface;
+    use screen::properties::DisplayRotation;
+
+    let mut screen = screen::sh1106::Sh1106::new(MockInterface::<4, 0>::new());
+    screen.set_logical_rotation(DisplayRotation::Rotate90);
+
+    assert!(matches!(screen.get_rotation(), DisplayRotation::Rotate90));
+    assert_eq!(screen.dirty_bounds(), Some(((0, 0), (63, 127))));
+    assert!(screen.release().commands().is_empty());
+}
+
+#[test]
+fn flush_sends_full_width_bottom_row() {
+    use crate::{command::Command, command::Page, interface::mock::MockInterface};
+
+    let mut screen = screen::sh1106::Sh1106::new(MockInterface::<16, 1024>::new());
+    screen.get_mut_canvas().set_pixel(5, 0, true);
+    screen.get_mut_canvas().draw_hline(0, 63, 128, true);
+    screen.flush().unwrap();
+
+    let mock = screen.release();
+    assert_eq!(
+        mock.commands(),
+        [
+            Command::PageAddress(Page::Page0),
+            Command::ColumnAddress(7),
+            Command::PageAddress(Page::Page7),
+            Command::ColumnAddress(2),
+        ]
+    );
+    assert_eq!(mock.data()[0], 0x01);
+    assert_eq!(mock.data().len(), 1 + 128);
+    assert!(mock.data()[1..].iter().all(|&byte| byte == 0x80));
+}
+
+#[test]
+fn flush_splits_wide_pages_for_i2c() {
+    use crate::tests::i2c::RecordingI2c;
+
+    let mut screen: screen::sh1106::Sh1106<_, 1056, 132, 64, 0> =
+        screen::sh1106::Sh1106::new_with_size(I2cInterface::new(RecordingI2c::default(), 0x3C));
+    screen.get_mut_canvas().draw_hline(0, 0, 132, true);
+    screen.flush().unwrap();
+
+    let i2c = screen.release().release();
+    assert_eq!(i2c.writes.len(), 4);
+    assert_eq!(i2c.writes[0].1, [0x00, 0xB0, 0x00, 0x10]);
+    assert_eq!(i2c.writes[1].1.len(), 1 + 128);
+    assert_eq!(i2c.writes[2].1, [0x00, 0xB0, 0x00, 0x18]);
+    assert_eq!(i2c.writes[3].1, [0x40, 0x01, 0x01, 0x01, 0x01]);
+}
+
+#[test]
+fn flush_handles_empty_and_minimal_dirty_areas() {
+    use crate::{command::Command, command::Page, interface::mock::MockInterface};
+
+    let mut screen = screen::sh1106::Sh1106::new(MockInterface::<32, 1024>::new());
+    assert_eq!(screen.dirty_bounds(), None);
+    screen.flush().unwrap();
+    assert_eq!(screen.release().commands(), []);
+
+    let mut screen = screen::sh1106::Sh1106::new(MockInterface::<32, 1024>::new());
+
+    screen.flush_all().unwrap();
+    screen.flush().unwrap();
+    let mut mock = screen.release();
+    assert_eq!(mock.commands().len(), 8 * 2);
+    assert_eq!(mock.data_writes(), 8);
+    assert_eq!(mock.data().len(), 1024);
+    assert!(mock.data().iter().all(|&byte| byte == 0x00));
+
+    mock.clear();
+    let mut screen = screen::sh1106::Sh1106::new(mock);
+    screen.get_mut_canvas().set_pixel(0, 0, true);
+    assert_eq!(screen.dirty_bounds(), Some(((0, 0), (0, 0))));
+    screen.flush().unwrap();
+    assert_eq!(screen.dirty_bounds(), None);
+
+    let mock = screen.release();
+    assert_eq!(
+        mock.commands(),
+        [Command::PageAddress(Page::Page0), Command::ColumnAddress(2),]
+    );
+    assert_eq!(mock.data(), [0x01]);
+}
+
+#[test]
+fn dirty_bounds_in_rotated_mode() {
+    use crate::interface::mock::MockInterface;
+    use screen::properties::DisplayRotation;
+
+    let mut screen = screen::sh1106::Sh1106::new(MockInterface::<4, 0>::new());
+    screen.set_logical_rotation(DisplayRotation::Rotate90);
+    screen.get_mut_canvas().reset_dirty_area();
+
+    screen.get_mut_canvas().set_pixel(60, 100, true);
+    assert_eq!(screen.dirty_bounds(), Some(((60, 100), (60, 100))));
+}
+
+#[test]
+fn dirty_bounds_of_full_area_in_rotated_mode() {
+    use crate::interface::mock::MockInterface;
+    use screen::properties::{DisplayRotation, TestPattern};
+
+    let mut screen = screen::sh1106::Sh1106::new(MockInterface::<8, 0>::new());
+    screen.set_logical_rotation(DisplayRotation::Rotate90);
+    assert_eq!(screen.dirty_bounds(), Some(((0, 0), (63, 127))));
+
+    screen.set_mirror_x(true).unwrap();
+    screen
+        .get_mut_canvas()
+        .draw_test_pattern(TestPattern::Border);
+    assert_eq!(screen.dirty_bounds(), Some(((0, 0), (63, 127))));
+
+    screen.get_mut_canvas().reset_dirty_area();
+    screen.get_mut_canvas().buffer_guard()[0] = 0xFF;
+    assert_eq!(screen.dirty_bounds(), Some(((0, 0), (63, 127))));
+}
+
+#[test]
+fn new_with_rotation_sends_remap_in_init() {
+    use crate::{command::Command, interface::mock::MockInterface};
+    use screen::properties::DisplayRotation;
+
+    let mut screen = screen::sh1106::Sh1106::new_with_rotation(
+        MockInterface::<16, 0>::new(),
+        DisplayRotation::Rotate180,
+    );
+    screen.init().unwrap();
+    assert!(matches!(screen.get_rotation(), DisplayRotation::Rotate180));
+
+    let mock = screen.release();
+    assert_eq!(mock.commands().len(), 15);
+    assert_eq!(
+        mock.commands()[6..8],
+        [Command::DisableSegmentRemap, Command::DisableReverseComDir]
+    );
+}
+
+#[test]
+fn sleep_and_wake_switch_charge_pump() {
+    use crate::{command::Command, interface::mock::MockInterface};
+
+    let mut screen = screen::sh1106::Sh1106::new(MockInterface::<8, 0>::new());
+    screen.sleep().unwrap();
+    assert!(!screen.is_display_on());
+    screen.wake().unwrap();
+    assert!(screen.is_display_on());
+
+    let mock = screen.release();
+    assert_eq!(
+        mock.commands(),
+        [
+            Command::TurnDisplayOff,
+            Command::DisableChargePump,
+            Command::EnableChargePump,
+            Command::TurnDisplayOn,
+        ]
+    );
+}
+
+#[test]
+fn dimensions_follow_rotation() {
+    use screen::properties::DisplayRotation;
+
+    let mut screen = screen::sh1106::Sh1106::new(I2cInterface::new(I2c0, 0x3C));
+    assert_eq!(screen.dimensions(), (128, 64));
+
+    screen.set_logical_rotation(DisplayRotation::Rotate270);
+    assert_eq!(screen.dimensions(), (64, 128));
+    assert_eq!(screen.physical_dimensions(), (128, 64));
+}
+
+#[test]
+fn flush_paced_waits_frame_interval() {
+    use crate::{
+        interface::mock::MockInterface, screen::timer::FrameTimer, tests::hal::RecordingDelay,
+    };
+
+    let mut screen = screen::sh1106::Sh1106::new(MockInterface::<4, 8>::new());
+    let mut delay = RecordingDelay::default();
+    let timer = FrameTimer::new(30);
+
+    screen.get_mut_canvas().set_pixel(0, 0, true);
+    screen.flush_paced(&mut delay, &timer).unwrap();
+
+    assert_eq!(screen.dirty_bounds(), None);
+    assert_eq!(screen.release().data(), [0x01]);
+    assert_eq!(delay.delays_ns, [33_333_000]);
+    assert_eq!(FrameTimer::new(0).frame_interval_us(), 1_000_000);
+}
+
+#[test]
+fn init_with_sends_modified_default_sequence() {
+    use crate::{command::Command, interface::mock::MockInterface};
+
+    let mut screen = screen::sh1106::Sh1106::new(MockInterface::<32, 8>::new());
+    screen.init().unwrap();
+    let default_commands = screen.release().commands().to_vec();
+
+    let mut screen = screen::sh1106::Sh1106::new(MockInterface::<32, 8>::new());
+    let mut sequence = screen.default_init();
+    assert_eq!(sequence[9], Command::Contrast(0x80));
+    sequence[9] = Command::Contrast(0x40);
+    screen.init_with(sequence).unwrap();
+
+    assert_eq!(screen.get_contrast(), 0x40);
+    assert!(screen.is_display_on());
+    let mut commands = screen.release().commands().to_vec();
+    assert_eq!(commands[9], Command::Contrast(0x40));
+    commands[9] = Command::Contrast(0x80);
+    assert_eq!(commands, default_commands);
+}
+
+#[test]
+fn scroll_diagonal_sends_setup_sequence() {
+    use crate::{
+        command::{Command, HorizontalScrollDirection, NFrames, Page},
+        error::MiniOledError,
+        interface::mock::MockInterface,
+        screen::properties::Controller,
+    };
+
+    // The SH1106 has no scroll commands.
+    let mut screen = screen::sh1106::Sh1106::new(MockInterface::<8, 8>::new());
+    assert_eq!(
+        screen.scroll_diagonal(
+            HorizontalScrollDirection::Right,
+            Page::Page0,
+            Page::Page7,
+            NFrames::F5,
+            1,
+        ),
+        Err(MiniOledError::UnsupportedOperation)
+    );
+    assert!(screen.release().commands().is_empty());
+
+    let mut screen = screen::builder::Sh1106Builder::new()
+        .controller(Controller::Ssd1306)
+        .build(MockInterface::<8, 8>::new());
+    screen
+        .scroll_diagonal(
+            HorizontalScrollDirection::Right,
+            Page::Page0,
+            Page::Page7,
+            NFrames::F5,
+            1,
+        )
+        .unwrap();
+
+    assert_eq!(
+        screen.release().commands(),
+        [
+            Command::DisableScroll,
+            Command::VerticalScrollArea {
+                top_fixed_rows: 0,
+                scroll_rows: 64,
+            },
+            Command::VerticalAndHorizontalScrollSetup {
+                direction: HorizontalScrollDirection::Right,
+                start_page: Page::Page0,
+                interval: NFrames::F5,
+                end_page: Page::Page7,
+                vertical_offset: 1,
+            },
+            Command::EnableScroll,
+        ]
+    );
+}
+
+#[test]
+fn init_with_rejects_invalid_multiplex_before_sending() {
+    use crate::{
+        command::{Command, CommandBuffer},
+        error::MiniOledError,
+        interface::CommunicationInterface,
+    };
+
+    /// Interface that sends commands without validating them.
+    #[derive(Default)]
+    struct UncheckedInterface {
+        command_writes: usize,
+    }
+
+    impl CommunicationInterface for UncheckedInterface {
+        fn init(&mut self) -> Result<(), MiniOledError> {
+            Ok(())
+        }
+
+        fn write_command<const N: usize>(
+            &mut self,
+            _buf: &CommandBuffer<N>,
+        ) -> Result<(), MiniOledError> {
+            self.command_writes += 1;
+            Ok(())
+        }
+
+        fn write_data(&mut self, _buf: &[u8]) -> Result<(), MiniOledError> {
+            Ok(())
+        }
+    }
+
+    let mut screen = screen::sh1106::Sh1106::new(UncheckedInterface::default());
+    let mut sequence = screen.default_init();
+    sequence[2] = Command::Multiplex(7);
+
+    assert_eq!(
+        screen.init_with(sequence),
+        Err(MiniOledError::InvalidCommandArgument)
+    );
+    assert!(!screen.is_display_on());
+    screen.init().unwrap();
+    assert_eq!(screen.release().command_writes, 1);
+}
+
+#[test]
+fn flush_all_sends_full_width_pages_and_clears_dirty_area() {
+    use crate::{command::Command, command::Page, interface::mock::MockInterface};
+
+    let mut screen = screen::sh1106::Sh1106::new(MockInterface::<32, 1024>::new());
+    screen.get_mut_canvas().set_pixel(5, 5, true);
+    screen.flush_all().unwrap();
+    assert_eq!(screen.dirty_bounds(), None);
+
+    let mock = screen.release();
+    assert_eq!(mock.data_writes(), 8);
+    assert_eq!(mock.data().len(), 1024);
+    assert_eq!(mock.data()[5], 0b10_0000);
+    assert_eq!(
+        mock.commands()[14..],
+        [Command::PageAddress(Page::Page7), Command::ColumnAddress(2),]
+    );
+}
+
+#[test]
+fn init_with_delay_waits_after_charge_pump_and_display_on() {
+    use crate::{interface::mock::MockInterface, tests::hal::RecordingDelay};
+
+    let mut screen = screen::sh1106::Sh1106::new(MockInterface::<32, 8>::new());
+    screen.init().unwrap();
+    let default_commands = screen.release().commands().to_vec();
+
+    let mut screen = screen::sh1106::Sh1106::new(MockInterface::<32, 8>::new());
+    let mut delay = RecordingDelay::default();
+    screen.init_with_delay(&mut delay).unwrap();
+
+    assert!(screen.is_display_on());
+    assert_eq!(delay.delays_ns, [5_000_000, 100_000_000]);
+    assert_eq!(screen.release().commands(), default_commands);
+}
+
+#[test]
+fn initial_contrast_is_sent_inside_init_sequence() {
+    use crate::tests::i2c::RecordingI2c;
+
+    let mut screen = screen::builder::Sh1106Builder::new()
+        .contrast(0x10)
+        .build(I2cInterface::new(RecordingI2c::default(), 0x3C));
+    assert_eq!(screen.get_contrast(), 0x10);
+    screen.init().unwrap();
+    assert_eq!(screen.get_contrast(), 0x10);
+
+    let i2c = screen.release().release();
+    assert_eq!(i2c.writes.len(), 1);
+    assert!(
+        i2c.writes[0]
+            .1
+            .windows(2)
+            .any(|bytes| bytes == [0x81, 0x10])
+    );
+}
+
+#[test]
+fn modify_region_flushes_changes() {
+    use crate::interface::mock::MockInterface;
+
+    let mut screen = screen::sh1106::Sh1106::new(MockInterface::<8, 8>::new());
+    let drawn = screen
+        .modify_region(|canvas| {
+            canvas.set_pixel(0, 0, true);
+            canvas.set_pixel(1, 0, true);
+            2
+        })
+        .unwrap();
+
+    assert_eq!(drawn, 2);
+    assert_eq!(screen.dirty_bounds(), None);
+    let mock = screen.release();
+    assert_eq!(mock.commands().len(), 2);
+    assert_eq!(mock.data(), [0x01, 0x01]);
+}
+
+#[test]
+fn read_status_and_is_busy() {
+    use crate::{error::MiniOledError, interface::mock::MockInterface, tests::i2c::RecordingI2c};
+
+    let i2c = RecordingI2c {
+        status: 0xC0,
+        ..Default::default()
+    };
+    let mut screen = screen::sh1106::Sh1106::new(I2cInterface::new(i2c, 0x3C));
+    assert_eq!(screen.read_status(), Ok(0xC0));
+    assert_eq!(screen.is_busy(), Ok(true));
+    let i2c = screen.release().release();
+    assert_eq!(i2c.writes[0], (0x3C, std::vec![0x00]));
+
+    let mut screen = screen::sh1106::Sh1106::new(MockInterface::<8, 8>::new());
+    assert_eq!(screen.is_busy(), Err(MiniOledError::UnsupportedOperation));
+}
+
+#[test]
+fn flush_returns_sent_bytes_and_skips_clean_canvas() {
+    use crate::interface::mock::MockInterface;
+
+    let mut screen = screen::sh1106::Sh1106::new(MockInterface::<8, 16>::new());
+    assert!(!screen.get_canvas().is_dirty());
+    assert_eq!(screen.flush(), Ok(0));
+
+    screen.get_mut_canvas().draw_hline(4, 0, 10, true);
+    assert!(screen.get_canvas().is_dirty());
+    assert_eq!(screen.flush(), Ok(10));
+    assert!(!screen.get_canvas().is_dirty());
+    assert_eq!(screen.flush(), Ok(0));
+
+    let mock = screen.release();
+    assert_eq!(mock.commands().len(), 2);
+    assert_eq!(mock.data_writes(), 1);
+}
+
+#[test]
+fn reset_to_defaults_restores_init_state() {
+    use crate::{command::Command, interface::mock::MockInterface, screen::properties::Controller};
+
+    for controller in [Controller::Sh1106, Controller::Ssd1306] {
+        let mut screen = screen::builder::Sh1106Builder::new()
+            .controller(controller)
+            .build(MockInterface::<64, 1024>::new());
+        screen.init().unwrap();
+        screen.set_contrast(0x10).unwrap();
+        screen.set_inverted(true).unwrap();
+        screen
+            .set_rotation(screen::properties::DisplayRotation::Rotate90)
+            .unwrap();
+        screen.get_mut_canvas().set_pixel(1, 1, true);
+        screen.reset_to_defaults().unwrap();
+
+        assert_eq!(screen.get_contrast(), 0x80);
+        assert!(!screen.is_inverted());
+        assert_eq!(screen.dimensions(), (128, 64));
+        let init_sequence = screen.default_init();
+
+        let mock = screen.release();
+        let reset_start = mock
+            .commands()
+            .windows(init_sequence.len())
+            .rposition(|commands| *commands == init_sequence)
+            .unwrap();
+        // The SH1106 has no scroll to stop.
+        assert_eq!(
+            mock.commands()[reset_start - 1] == Command::DisableScroll,
+            controller == Controller::Ssd1306,
+            "{controller:?}"
+        );
+        assert_eq!(init_sequence[6], Command::EnableSegmentRemap);
+        assert!(
+            mock.data()[mock.data().len() - 1024..]
+                .iter()
+                .all(|&byte| byte == 0)
+        );
+    }
+}
+
+#[cfg(feature = "heapless")]
+#[test]
+fn send_dyn_commands_sends_every_command() {
+    use crate::{
+        command::{Command, DynCommandBuffer},
+        error::MiniOledError,
+        interface::mock::MockInterface,
+    };
+
+    let mut screen = screen::sh1106::Sh1106::new(MockInterface::<8, 8>::new());
+    let mut commands: DynCommandBuffer<4> = DynCommandBuffer::new();
+    commands
+        .extend([Command::Contrast(0x10), Command::TurnDisplayOn])
+        .unwrap();
+    screen.send_dyn_commands(&commands).unwrap();
+
+    commands.push(Command::StartLine(64)).unwrap();
+    assert_eq!(
+        screen.send_dyn_commands(&commands),
+        Err(MiniOledError::InvalidCommandArgument)
+    );
+    assert_eq!(
+        screen.release().commands(),
+        [Command::Contrast(0x10), Command::TurnDisplayOn]
+    );
+}
+
+#[test]
+fn set_brightness_follows_quadratic_curve() {
+    use crate::interface::mock::MockInterface;
+
+    let mut screen = screen::sh1106::Sh1106::new(MockInterface::<8, 8>::new());
+    for (percent, contrast) in [(0, 0), (10, 3), (50, 64), (100, 255), (200, 255)] {
+        screen.set_brightness(percent).unwrap();
+        assert_eq!(screen.get_contrast(), contrast);
+    }
+}
+
+#[test]
+fn mirror_toggles_panel_direction_relative_to_rotation() {
+    use crate::{command::Command, interface::mock::MockInterface};
+
+    let mut screen = screen::sh1106::Sh1106::new(MockInterface::<64, 1024>::new());
+    screen.set_mirror_x(true).unwrap();
+    screen.set_mirror_y(true).unwrap();
+    screen
+        .set_rotation(screen::properties::DisplayRotation::Rotate180)
+        .unwrap();
+    screen.init().unwrap();
+    screen.get_mut_canvas().set_pixel(0, 0, true);
+    // The controller mirrors and rotates, so the buffer mapping doesn't change.
+    assert_eq!(screen.get_canvas().get_buffer()[0], 0x01);
+
+    let mock = screen.release();
+    assert_eq!(
+        mock.commands()[..4],
+        [
+            Command::DisableSegmentRemap,
+            Command::DisableReverseComDir,
+            Command::EnableSegmentRemap,
+            Command::EnableReverseComDir,
+        ]
+    );
+    assert_eq!(
+        mock.commands()[10..12],
+        [Command::EnableSegmentRemap, Command::EnableReverseComDir]
+    );
+}
+
+#[test]
+fn mirror_after_logical_rotation_keeps_panel_rotation() {
+    use crate::{command::Command, interface::mock::MockInterface};
+    use screen::properties::DisplayRotation;
+
+    /// Returns where the only lit pixel of `buffer` appears on the glass, with the last
+    /// segment remap and COM scan direction in `commands`.
+    fn lit_on_glass(buffer: &[u8], commands: &[Command]) -> (usize, usize) {
+        let segment_remap = commands.iter().rev().find_map(|command| match command {
+            Command::EnableSegmentRemap => Some(true),
+            Command::DisableSegmentRemap => Some(false),
+            _ => None,
+        });
+        let reverse_com = commands.iter().rev().find_map(|command| match command {
+            Command::EnableReverseComDir => Some(true),
+            Command::DisableReverseComDir => Some(false),
+            _ => None,
+        });
+        let idx = buffer.iter().position(|&byte| byte != 0).unwrap();
+        let (column, row) = (
+            idx % 128,
+            (idx / 128) * 8 + buffer[idx].trailing_zeros() as usize,
+        );
+        (
+            if segment_remap.unwrap() {
+                127 - column
+            } else {
+                column
+            },
+            if reverse_com.unwrap() { 63 - row } else { row },
+        )
+    }
+
+    for (mirror_x, mirror_y, sent) in [
+        (true, false, Command::DisableSegmentRemap),
+        (false, true, Command::DisableReverseComDir),
+    ] {
+        let mut screen = screen::sh1106::Sh1106::new(MockInterface::<8, 0>::new());
+        screen.set_rotation(DisplayRotation::Rotate0).unwrap();
+        screen.set_logical_rotation(DisplayRotation::Rotate90);
+        screen.set_mirror_x(mirror_x).unwrap();
+        screen.set_mirror_y(mirror_y).unwrap();
+        screen.get_mut_canvas().set_pixel(5, 20, true);
+        let buffer = *screen.get_canvas().get_buffer();
+        let mock = screen.release();
+
+        // Toggled relative to the `Rotate0` the panel is set for.
+        assert!(mock.commands()[2..].contains(&sent));
+
+        let mut expected = screen::sh1106::Sh1106::new(MockInterface::<8, 0>::new());
+        expected.set_rotation(DisplayRotation::Rotate90).unwrap();
+        expected.set_mirror_x(mirror_x).unwrap();
+        expected.set_mirror_y(mirror_y).unwrap();
+        expected.get_mut_canvas().set_pixel(5, 20, true);
+        let expected_buffer = *expected.get_canvas().get_buffer();
+
+        assert_eq!(
+            lit_on_glass(&buffer, mock.commands()),
+            lit_on_glass(&expected_buffer, expected.release().commands())
+        );
+    }
+}
+
+#[test]
+fn start_line_animator_wraps_around_ram_rows() {
+    use crate::{
+        command::Command, interface::mock::MockInterface,
+        screen::animator::DisplayStartLineAnimator,
+    };
+
+    let mut screen = screen::sh1106::Sh1106::new(MockInterface::<8, 8>::new());
+    let mut animator = DisplayStartLineAnimator::new(20);
+    for _ in 0..4 {
+        animator.step(&mut screen).unwrap();
+    }
+    assert_eq!(animator.line(), 16);
+    animator.reset(&mut screen).unwrap();
+
+    assert_eq!(
+        screen.release().commands(),
+        [20, 40, 60, 16, 0].map(Command::StartLine)
+    );
+}
+
+#[test]
+fn flush_detailed_counts_pages_and_bytes() {
+    use crate::{
+        interface::i2c::I2cInterface, screen::canvas::FlushStats, tests::i2c::RecordingI2c,
+    };
+
+    let mut screen: screen::sh1106::Sh1106<_, 1024, 128, 64, 2> =
+        screen::sh1106::Sh1106::new(I2cInterface::new(RecordingI2c::default(), 0x3C));
+    screen.get_mut_canvas().set_pixel(3, 2, true);
+    screen.get_mut_canvas().set_pixel(5, 20, true);
+    assert_eq!(
+        screen.flush_detailed(),
+        Ok(FlushStats {
+            pages_sent: 2,
+            data_bytes: 2,
+            command_bytes: 6,
+        })
+    );
+    assert_eq!(screen.flush_detailed(), Ok(FlushStats::default()));
+}
+
+#[test]
+fn init_verified_checks_display_off_flag() {
+    use crate::{error::MiniOledError, tests::i2c::RecordingI2c};
+
+    for (status, result) in [
+        (0x00, Ok(())),
+        (0x40, Err(MiniOledError::InitializationError)),
+    ] {
+        let i2c = RecordingI2c {
+            status,
+            ..Default::default()
+        };
+        let mut screen = screen::sh1106::Sh1106::new(I2cInterface::new(i2c, 0x3C));
+        assert_eq!(screen.init_verified(), result);
+    }
+}
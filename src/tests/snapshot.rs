@@ -0,0 +1,124 @@
+use std::{string::String, vec::Vec};
+
+use crate::screen::{
+    canvas::Canvas,
+    properties::{DisplayProperties, DisplayRotation},
+};
+
+/// Small non-square canvas, so swapped axes show up in the snapshots.
+type SnapshotCanvas = Canvas<16, 16, 8, 0>;
+
+fn new_canvas(display_rotation: DisplayRotation) -> SnapshotCanvas {
+    Canvas::new(DisplayProperties::new(display_rotation))
+}
+
+/// Renders the canvas in logical orientation, one line per row, with `#` for a lit pixel
+/// and a space for a dark one. Trailing spaces are trimmed so fixtures don't depend on them.
+fn render_ascii<const N: usize, const W: u32, const H: u32, const O: u8>(
+    canvas: &Canvas<N, W, H, O>,
+) -> String {
+    let (width, height) = canvas.get_rotated_size();
+    let mut rows = Vec::new();
+    for y in 0..height {
+        let row: String = canvas
+            .pixels()
+            .skip((y * width) as usize)
+            .take(width as usize)
+            .map(|(_, _, on)| if on { '#' } else { ' ' })
+            .collect();
+        rows.push(String::from(row.trim_end()));
+    }
+    rows.join("\n")
+}
+
+/// Renders the buffer like `render_ascii`, in the column and row order of the controller
+/// RAM instead of the logical orientation.
+fn render_panel<const N: usize, const W: u32, const H: u32, const O: u8>(
+    canvas: &Canvas<N, W, H, O>,
+) -> String {
+    let mut rows = Vec::new();
+    for row in 0..H {
+        let line: String = (0..W)
+            .map(|column| {
+                let byte = canvas.get_buffer()[((row >> 3) * W + column) as usize];
+                if byte & (1 << (row & 7)) != 0 {
+                    '#'
+                } else {
+                    ' '
+                }
+            })
+            .collect();
+        rows.push(String::from(line.trim_end()));
+    }
+    rows.join("\n")
+}
+
+/// Joins fixture lines into the format of `render_ascii`.
+fn fixture(lines: &[&str]) -> String {
+    lines.join("\n")
+}
+
+#[test]
+fn set_pixel_snapshots_in_every_rotation() {
+    for (display_rotation, logical, panel) in [
+        (
+            DisplayRotation::Rotate0,
+            fixture(&["", "  #", "", "", "", "", "", "#"]),
+            fixture(&["", "  #", "", "", "", "", "", "#"]),
+        ),
+        (
+            DisplayRotation::Rotate180,
+            fixture(&["", "  #", "", "", "", "", "", "#"]),
+            fixture(&["", "  #", "", "", "", "", "", "#"]),
+        ),
+        (
+            DisplayRotation::Rotate90,
+            fixture(&[
+                "", "  #", "", "", "", "", "", "#", "", "", "", "", "", "", "", "",
+            ]),
+            fixture(&["       #", "", " #", "", "", "", "", ""]),
+        ),
+        (
+            DisplayRotation::Rotate270,
+            fixture(&[
+                "", "  #", "", "", "", "", "", "#", "", "", "", "", "", "", "", "",
+            ]),
+            fixture(&["       #", "", " #", "", "", "", "", ""]),
+        ),
+    ] {
+        let mut canvas = new_canvas(display_rotation);
+        canvas.set_pixel(2, 1, true);
+        canvas.set_pixel(0, 7, true);
+
+        assert_eq!(render_ascii(&canvas), logical, "{display_rotation:?}");
+        assert_eq!(render_panel(&canvas), panel, "{display_rotation:?}");
+    }
+}
+
+#[cfg(feature = "embedded-graphics-core")]
+#[test]
+fn rectangle_outline_snapshot() {
+    use embedded_graphics::{
+        pixelcolor::BinaryColor,
+        prelude::*,
+        primitives::{PrimitiveStyle, Rectangle},
+    };
+
+    for display_rotation in [DisplayRotation::Rotate0, DisplayRotation::Rotate90] {
+        let mut canvas = new_canvas(display_rotation);
+        Rectangle::new(Point::new(1, 1), Size::new(5, 4))
+            .into_styled(PrimitiveStyle::with_stroke(BinaryColor::On, 1))
+            .draw(&mut canvas)
+            .unwrap();
+
+        let (_, height) = canvas.get_rotated_size();
+        let mut expected = std::vec![" #####", " #   #", " #   #", " #####"];
+        expected.insert(0, "");
+        expected.resize(height as usize, "");
+        assert_eq!(
+            render_ascii(&canvas),
+            fixture(&expected),
+            "{display_rotation:?}"
+        );
+    }
+}
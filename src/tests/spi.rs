@@ -0,0 +1,39 @@
+use std::vec::Vec;
+
+use embedded_hal::spi::{self, Operation, SpiDevice};
+
+/// SPI device stub which records the words of every write transaction, bytes by default.
+#[derive(Default)]
+pub struct RecordingSpi<W = u8> {
+    pub writes: Vec<Vec<W>>,
+}
+
+impl<W> spi::ErrorType for RecordingSpi<W> {
+    type Error = spi::ErrorKind;
+}
+
+impl<W: Copy + 'static> SpiDevice<W> for RecordingSpi<W> {
+    fn transaction(&mut self, operations: &mut [Operation<'_, W>]) -> Result<(), Self::Error> {
+        let mut bytes = Vec::new();
+        for operation in operations {
+            if let Operation::Write(write) = operation {
+                bytes.extend_from_slice(write);
+            }
+        }
+        self.writes.push(bytes);
+        Ok(())
+    }
+}
+
+/// SPI device stub whose transactions always fail.
+pub struct FailingSpi;
+
+impl spi::ErrorType for FailingSpi {
+    type Error = spi::ErrorKind;
+}
+
+impl SpiDevice for FailingSpi {
+    fn transaction(&mut self, _operations: &mut [Operation<'_, u8>]) -> Result<(), Self::Error> {
+        Err(spi::ErrorKind::Overrun)
+    }
+}